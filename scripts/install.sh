@@ -64,8 +64,8 @@ cd ~/.config/voice-input
 # 运行程序（使用绝对路径，输出到日志）
 $HOME/.local/bin/voice-input 2>&1 | tee -a ~/.config/voice-input/voice-input.log
 
-# 如果程序异常退出，显示通知
-if [ ${PIPESTATUS[0]} -ne 0 ]; then
+# 普通错误由程序自己发送桌面通知；这里只处理崩溃（被信号终止）的情况
+if [ ${PIPESTATUS[0]} -ge 128 ]; then
     notify-send "语音输入" "程序异常退出，请查看日志" -u critical
 fi
 EOF
@@ -98,4 +98,9 @@ echo "  运行以下命令自动配置（快捷键: Super+Shift+B）："
 echo ""
 echo "  ./scripts/configure-shortcut.sh"
 echo ""
+echo "方法3 - 内置快捷键（KDE / sway / i3 等任意桌面）："
+echo "  在配置文件 [hotkey] 段设置 enabled = true，然后运行："
+echo ""
+echo "  $BINARY_NAME daemon"
+echo ""
 echo "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━"
@@ -72,7 +72,8 @@ impl SilenceDetector {
                     is_silent
                 }
                 None => {
-                    // 从未检测到声音（刚开始录音）
+                    // 从未检测到声音（刚开始录音），从此刻开始计时
+                    self.last_sound_time = Some(Instant::now());
                     false
                 }
             }
@@ -146,6 +147,22 @@ mod tests {
         assert!(detector.detect(&silent_samples)); // 超时后应返回 true
     }
 
+    #[test]
+    fn test_silence_from_start() {
+        // 从开始录音就一直没有声音：从第一次检测开始计时，超时后同样判定为静音
+        let mut detector = SilenceDetector::new(0.1, Duration::from_millis(100));
+        let silent_samples = vec![0.0; 1000];
+        assert!(!detector.detect(&silent_samples));
+        assert!(!detector.detect(&silent_samples));
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(detector.detect(&silent_samples));
+
+        // 重置后重新计时
+        detector.reset();
+        assert!(!detector.detect(&silent_samples));
+    }
+
     #[test]
     fn test_sound_detection() {
         let mut detector = SilenceDetector::new(0.1, Duration::from_millis(100));
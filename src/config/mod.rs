@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use crate::daemon::DaemonCommand;
+
 /// 应用配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub whisper: WhisperConfig,
@@ -12,6 +15,31 @@ pub struct Config {
     pub audio: AudioConfig,
     #[serde(default)]
     pub output: OutputConfig,
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+    #[serde(default)]
+    pub push_to_talk: PushToTalkConfig,
+    #[serde(default)]
+    pub hotkey: HotkeyConfig,
+    #[serde(default)]
+    pub notification: NotificationConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub confidence: ConfidenceConfig,
+    #[serde(default)]
+    pub processing: ProcessingConfig,
+    #[serde(default)]
+    pub markdown: MarkdownConfig,
+    #[serde(default)]
+    pub code: CodeConfig,
+    #[serde(default)]
+    pub editing: EditingConfig,
+    #[serde(default)]
+    pub command: CommandConfig,
+    /// 语音命令（command 模式）
+    #[serde(default)]
+    pub commands: Vec<VoiceCommand>,
 }
 
 /// Whisper 离线识别配置
@@ -35,6 +63,21 @@ pub struct XFyunConfig {
     pub app_id: String,
     pub api_secret: String,
     pub api_key: String,
+    /// 识别语言：zh_cn | en_us
+    #[serde(default = "default_xfyun_language")]
+    pub language: String,
+    /// 方言：mandarin | cantonese 等（仅中文有效）
+    #[serde(default = "default_xfyun_accent")]
+    pub accent: String,
+    /// 每个词返回的候选数（1-5，大于 1 时可用 --pick 选择候选词）
+    #[serde(default = "default_xfyun_candidates")]
+    pub candidates: u8,
+    /// 是否由识别引擎自动添加标点（关闭后可用口述标点，见 spoken_punctuation）
+    #[serde(default = "default_true")]
+    pub punctuation: bool,
+    /// 结束词：识别结果以其中之一结尾时立即结束录音，并从结果中去掉
+    #[serde(default)]
+    pub stop_keywords: Vec<String>,
 }
 
 /// 音频配置
@@ -55,28 +98,437 @@ pub struct OutputConfig {
     pub default: String,
     /// 文件输出路径（可选）
     pub file_path: Option<String>,
+    /// 流式输出：边识别边向焦点窗口输入已稳定的文字
+    #[serde(default)]
+    pub streaming: bool,
+    /// 键盘模拟工具：xdotool | wtype | ydotool
+    #[serde(default = "default_typing_tool")]
+    pub typing_tool: String,
+}
+
+/// 守护进程配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonConfig {
+    /// 是否在 D-Bus 会话总线上注册服务
+    pub dbus: bool,
+}
+
+/// 按住说话配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushToTalkConfig {
+    /// 按键名（evdev 键名，如 KEY_RIGHTCTRL）
+    pub key: String,
+    /// 输入设备路径（为空时自动查找带有该按键的键盘）
+    #[serde(default)]
+    pub device: Option<String>,
 }
 
-impl Default for Config {
+/// 全局快捷键配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyConfig {
+    /// 守护进程是否自行监听快捷键
+    pub enabled: bool,
+    /// 监听方式：evdev | portal
+    pub backend: String,
+    /// evdev 输入设备路径（为空时自动查找）
+    #[serde(default)]
+    pub device: Option<String>,
+    /// 快捷键绑定
+    pub bindings: Vec<HotkeyBinding>,
+}
+
+/// 一个快捷键绑定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    /// 组合键，如 `Super+Shift+B`
+    pub chord: String,
+    /// 触发的命令：toggle | start | stop | cancel
+    pub action: DaemonCommand,
+}
+
+/// 桌面通知配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// 是否发送桌面通知（开始录音、识别结果、错误）
+    pub enabled: bool,
+}
+
+/// 听写历史配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// 是否保存识别结果
+    pub enabled: bool,
+    /// 数据库路径（为空时使用 ~/.local/share/voice-input/history.db）
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// 低置信度词标记配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidenceConfig {
+    /// 是否标记低置信度的词（文件输出加标记，终端中高亮）
+    pub enabled: bool,
+    /// 低于该分数的词会被标记（0.0-1.0）
+    pub threshold: f64,
+    /// 文件输出中包围低置信度词的标记
+    pub marker_start: String,
+    pub marker_end: String,
+}
+
+/// 文本后处理配置：识别结果按顺序经过各个处理步骤后再输出
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingConfig {
+    pub stages: Vec<StageConfig>,
+}
+
+/// 一个处理步骤
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageConfig {
+    /// 是否启用
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub stage: Stage,
+}
+
+/// 处理步骤类型（配置中的 `type` 字段）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Stage {
+    /// 去掉首尾空白
+    Trim,
+    /// 口述标点与排版命令（"逗号" → "，"、"换行" → 换行符）
+    SpokenPunctuation {
+        /// 是否使用内置的中英文命令表
+        #[serde(default = "default_true")]
+        builtin: bool,
+        /// 自定义命令（说法 → 替换文本），与内置命令重名时覆盖
+        #[serde(default)]
+        commands: BTreeMap<String, String>,
+        /// 转义词：说 "字面 逗号" 时保留 "逗号" 二字
+        #[serde(default = "default_spoken_escape")]
+        escape: Vec<String>,
+    },
+    /// 去掉语气词（嗯、呃、uh）、句首口头禅（那个、就是说）和口吃重复
+    Disfluency {
+        /// 额外的口头禅（只在句首且后面有逗号时去掉）
+        #[serde(default)]
+        fillers: Vec<String>,
+        /// 是否合并口吃重复（我我我 → 我）
+        #[serde(default = "default_true")]
+        repeats: bool,
+    },
+    /// 用户词表：按拼音模糊匹配同音误识别，替换为词表中的写法
+    Vocabulary {
+        /// 词表文件（默认 ~/.config/voice-input/vocabulary.txt）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        /// 拼音相似度阈值（0～1，越大越严格）
+        #[serde(default = "default_vocabulary_threshold")]
+        threshold: f64,
+    },
+    /// 反向文本规范化：中英文数词写成阿拉伯数字
+    Itn {
+        /// 启用的类别（默认全部）
+        #[serde(default = "default_itn_classes")]
+        classes: Vec<ItnClass>,
+    },
+    /// 标点规范化
+    Punctuation {
+        #[serde(default)]
+        style: PunctuationStyle,
+    },
+    /// 全角 / 半角转换（字母、数字、空格）
+    Width {
+        #[serde(default)]
+        to: WidthTarget,
+    },
+    /// 中英文、数字之间加空格（"用Rust写" → "用 Rust 写"）
+    Spacing {
+        /// 数字与汉字之间是否也加空格
+        #[serde(default = "default_true")]
+        numbers: bool,
+    },
+    /// 纯英文结果句首大写、单独的 i 写成 I
+    Capitalize,
+    /// 简繁转换（含台湾、香港用语）
+    ChineseScript {
+        #[serde(default)]
+        to: ScriptTarget,
+    },
+    /// 固定替换与片段展开（按顺序执行）
+    Replace {
+        #[serde(default)]
+        rules: Vec<ReplaceRule>,
+    },
+    /// 短语去掉末尾句号
+    StripTrailingPeriod {
+        /// 不超过该字数（不含句号）视为短语
+        #[serde(default = "default_short_phrase_chars")]
+        max_chars: usize,
+    },
+}
+
+/// 一条替换规则
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaceRule {
+    /// 要替换的文字（`regex = true` 时为正则表达式）
+    pub from: String,
+    /// 替换为（可以多行；正则规则中用 `$1`、`${name}` 引用捕获组）
+    pub to: String,
+    /// `from` 是否为正则表达式
+    #[serde(default)]
+    pub regex: bool,
+}
+
+/// Markdown 听写模式配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarkdownConfig {
+    /// 累积文档的保存路径（为空时使用 ~/.local/share/voice-input/document.md）
+    #[serde(default)]
+    pub document: Option<String>,
+}
+
+/// 代码听写模式配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeConfig {
+    /// 识别语言（默认 zh_cn 中英混合识别，中英文命令词都能用；只说英文时可用 en_us）
+    #[serde(default = "default_code_language")]
+    pub language: String,
+    /// 自定义符号（说法 → 输出文本）
+    #[serde(default)]
+    pub symbols: BTreeMap<String, String>,
+}
+
+impl Default for CodeConfig {
+    fn default() -> Self {
+        Self {
+            language: default_code_language(),
+            symbols: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_code_language() -> String {
+    "zh_cn".to_string()
+}
+
+/// 语音编辑命令配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EditingConfig {
+    /// 是否把 "把X改成Y"、"撤销" 等整句识别为对上一次结果的修改
+    pub enabled: bool,
+}
+
+/// 语音命令模式配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandConfig {
+    /// 模糊匹配的相似度阈值（0～1）
+    #[serde(default = "default_command_threshold")]
+    pub threshold: f64,
+    /// 危险命令等待确认的时长（秒）
+    #[serde(default = "default_confirm_timeout")]
+    pub confirm_timeout: u64,
+    /// 执行日志路径（为空时使用 ~/.local/share/voice-input/commands.log）
+    #[serde(default)]
+    pub log: Option<String>,
+}
+
+impl Default for CommandConfig {
     fn default() -> Self {
         Self {
-            whisper: WhisperConfig::default(),
-            xfyun: XFyunConfig::default(),
-            audio: AudioConfig::default(),
-            output: OutputConfig::default(),
+            threshold: default_command_threshold(),
+            confirm_timeout: default_confirm_timeout(),
+            log: None,
         }
     }
 }
 
+fn default_command_threshold() -> f64 {
+    0.8
+}
+
+fn default_confirm_timeout() -> u64 {
+    30
+}
+
+/// 一条语音命令：说法 → shell 命令或 D-Bus 调用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceCommand {
+    /// 说法（任意一个匹配即可）
+    pub phrases: Vec<String>,
+    /// 匹配方式
+    #[serde(default, rename = "match")]
+    pub matching: CommandMatch,
+    /// shell 命令（`{1}`、`{name}` 替换为正则捕获的参数）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+    /// D-Bus 方法调用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dbus: Option<DbusCall>,
+    /// 执行前需要确认
+    #[serde(default)]
+    pub dangerous: bool,
+}
+
+/// 语音命令的匹配方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandMatch {
+    /// 忽略标点、空格和大小写后完全相同
+    #[default]
+    Exact,
+    /// 字形或拼音相近（容忍同音误识别）
+    Fuzzy,
+    /// 正则表达式整句匹配，捕获组作为参数
+    Regex,
+}
+
+/// D-Bus 方法调用
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DbusCall {
+    /// session | system
+    #[serde(default = "default_dbus_bus")]
+    pub bus: String,
+    pub service: String,
+    pub path: String,
+    pub interface: String,
+    pub method: String,
+    /// 参数：字符串、整数（int32）或布尔值
+    #[serde(default)]
+    pub args: Vec<DbusArg>,
+}
+
+fn default_dbus_bus() -> String {
+    "session".to_string()
+}
+
+/// D-Bus 调用参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DbusArg {
+    Bool(bool),
+    Int(i32),
+    Str(String),
+}
+
+/// ITN 转换类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItnClass {
+    /// 基数（二十三 → 23，个位数保持不变）
+    Cardinal,
+    /// 序数（第三 → 第3，twenty first → 21st）
+    Ordinal,
+    /// 日期（二零二六年十月十八号 → 2026年10月18号）
+    Date,
+    /// 时间（下午三点半 → 下午3:30）
+    Time,
+    /// 百分比（百分之五 → 5%）
+    Percent,
+    /// 金额（三块五 → 3.5元，five dollars → $5）
+    Currency,
+    /// 逐位读出的号码（幺三八… → 138…）
+    Phone,
+    /// 度量单位（五公里 → 5公里）
+    Unit,
+}
+
+impl ItnClass {
+    pub const ALL: [ItnClass; 8] = [
+        ItnClass::Cardinal,
+        ItnClass::Ordinal,
+        ItnClass::Date,
+        ItnClass::Time,
+        ItnClass::Percent,
+        ItnClass::Currency,
+        ItnClass::Phone,
+        ItnClass::Unit,
+    ];
+}
+
+/// 标点风格
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PunctuationStyle {
+    /// 中文语境中使用全角标点（，。？！：；）
+    #[default]
+    Chinese,
+    /// 全部使用英文半角标点
+    Ascii,
+}
+
+/// 宽度转换方向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WidthTarget {
+    /// 全角字母数字转半角
+    #[default]
+    Half,
+    /// 半角字母数字转全角
+    Full,
+}
+
+/// 简繁转换目标
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptTarget {
+    /// 简体
+    Simplified,
+    /// 通用繁体
+    #[default]
+    Traditional,
+    /// 台湾繁体（软件 → 軟體）
+    ZhTw,
+    /// 香港繁体（出租车 → 的士）
+    ZhHk,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_spoken_escape() -> Vec<String> {
+    vec!["字面".to_string(), "literal".to_string()]
+}
+
+fn default_itn_classes() -> Vec<ItnClass> {
+    ItnClass::ALL.to_vec()
+}
+
+fn default_vocabulary_threshold() -> f64 {
+    0.8
+}
+
+fn default_short_phrase_chars() -> usize {
+    8
+}
+
+fn default_xfyun_language() -> String {
+    "zh_cn".to_string()
+}
+
+fn default_xfyun_accent() -> String {
+    "mandarin".to_string()
+}
+
+fn default_xfyun_candidates() -> u8 {
+    1
+}
+
+fn default_typing_tool() -> String {
+    "xdotool".to_string()
+}
+
 impl Default for WhisperConfig {
     fn default() -> Self {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
 
         // 多路径查找模型文件（优先级顺序）
-        let model_paths = vec![
+        let model_paths = [
             format!("{}/.local/share/whisper/ggml-base.bin", home),  // XDG 规范
             format!("{}/.cache/whisper/ggml-base.bin", home),        // 兼容性
-            format!("./models/ggml-base.bin"),                        // 本地目录
+            "./models/ggml-base.bin".to_string(),                     // 本地目录
         ];
 
         let model_path = model_paths
@@ -104,6 +556,11 @@ impl Default for XFyunConfig {
             app_id: std::env::var("XFYUN_APP_ID").unwrap_or_default(),
             api_secret: std::env::var("XFYUN_API_SECRET").unwrap_or_default(),
             api_key: std::env::var("XFYUN_API_KEY").unwrap_or_default(),
+            language: default_xfyun_language(),
+            accent: default_xfyun_accent(),
+            candidates: default_xfyun_candidates(),
+            punctuation: true,
+            stop_keywords: Vec::new(),
         }
     }
 }
@@ -123,10 +580,166 @@ impl Default for OutputConfig {
         Self {
             default: "clipboard".to_string(),
             file_path: None,
+            streaming: false,
+            typing_tool: default_typing_tool(),
+        }
+    }
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self { dbus: true }
+    }
+}
+
+impl Default for PushToTalkConfig {
+    fn default() -> Self {
+        Self {
+            key: "KEY_RIGHTCTRL".to_string(),
+            device: None,
+        }
+    }
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: "evdev".to_string(),
+            device: None,
+            bindings: vec![HotkeyBinding {
+                chord: "Super+Shift+B".to_string(),
+                action: DaemonCommand::Toggle,
+            }],
+        }
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+        }
+    }
+}
+
+impl Default for ConfidenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.6,
+            marker_start: "[[".to_string(),
+            marker_end: "]]".to_string(),
+        }
+    }
+}
+
+impl Default for ProcessingConfig {
+    fn default() -> Self {
+        let stage = |enabled, stage| StageConfig { enabled, stage };
+        Self {
+            stages: vec![
+                stage(true, Stage::Trim),
+                stage(
+                    false,
+                    Stage::SpokenPunctuation {
+                        builtin: true,
+                        commands: BTreeMap::new(),
+                        escape: default_spoken_escape(),
+                    },
+                ),
+                stage(
+                    false,
+                    Stage::Disfluency {
+                        fillers: Vec::new(),
+                        repeats: true,
+                    },
+                ),
+                stage(
+                    false,
+                    Stage::Vocabulary {
+                        path: None,
+                        threshold: default_vocabulary_threshold(),
+                    },
+                ),
+                stage(false, Stage::Itn { classes: default_itn_classes() }),
+                stage(false, Stage::Punctuation { style: PunctuationStyle::Chinese }),
+                stage(false, Stage::Width { to: WidthTarget::Half }),
+                stage(false, Stage::Spacing { numbers: true }),
+                stage(false, Stage::Capitalize),
+                stage(false, Stage::Replace { rules: Vec::new() }),
+                stage(false, Stage::ChineseScript { to: ScriptTarget::default() }),
+                stage(
+                    false,
+                    Stage::StripTrailingPeriod {
+                        max_chars: default_short_phrase_chars(),
+                    },
+                ),
+            ],
+        }
+    }
+}
+
+impl OutputConfig {
+    /// 是否输出到文件（否则复制到剪贴板）
+    pub fn to_file(&self) -> bool {
+        self.default == "file"
+    }
+}
+
+impl HistoryConfig {
+    /// 数据库文件路径（支持 ~ 展开）
+    pub fn db_path(&self) -> PathBuf {
+        match &self.path {
+            Some(path) => PathBuf::from(shellexpand::tilde(path).into_owned()),
+            None => {
+                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+                PathBuf::from(format!("{}/.local/share/voice-input/history.db", home))
+            }
+        }
+    }
+}
+
+impl CommandConfig {
+    /// 执行日志路径（支持 ~ 展开）
+    pub fn log_path(&self) -> PathBuf {
+        match &self.log {
+            Some(path) => PathBuf::from(shellexpand::tilde(path).into_owned()),
+            None => {
+                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+                PathBuf::from(format!("{}/.local/share/voice-input/commands.log", home))
+            }
         }
     }
 }
 
+impl MarkdownConfig {
+    /// 累积文档路径（支持 ~ 展开）
+    pub fn document_path(&self) -> PathBuf {
+        match &self.document {
+            Some(path) => PathBuf::from(shellexpand::tilde(path).into_owned()),
+            None => {
+                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+                PathBuf::from(format!("{}/.local/share/voice-input/document.md", home))
+            }
+        }
+    }
+}
+
+impl XFyunConfig {
+    /// 是否已配置全部 API 密钥
+    pub fn is_configured(&self) -> bool {
+        !self.app_id.is_empty() && !self.api_secret.is_empty() && !self.api_key.is_empty()
+    }
+}
+
 impl Config {
     /// 从文件加载配置
     pub fn from_file(path: impl Into<PathBuf>) -> crate::utils::Result<Self> {
@@ -142,23 +755,61 @@ impl Config {
 
     /// 加载配置（多路径查找）
     pub fn load() -> crate::utils::Result<Self> {
-        let config_paths = vec![
-            "./config.toml".to_string(),
-            "./voice-input.toml".to_string(),
-            format!("{}/.config/voice-input/config.toml",
-                std::env::var("HOME").unwrap_or_else(|_| ".".to_string())),
-            "/etc/voice-input/config.toml".to_string(),
+        match Self::find_path() {
+            Some(path) => {
+                tracing::info!("从配置文件加载: {}", path.display());
+                Self::from_file(path)
+            }
+            None => {
+                tracing::info!("未找到配置文件，使用默认配置");
+                Ok(Self::default())
+            }
+        }
+    }
+
+    /// 查找配置文件（按优先级返回第一个存在的路径）
+    pub fn find_path() -> Option<PathBuf> {
+        let config_paths = [
+            PathBuf::from("./config.toml"),
+            PathBuf::from("./voice-input.toml"),
+            Self::user_path(),
+            PathBuf::from("/etc/voice-input/config.toml"),
         ];
 
-        for path in config_paths {
-            if std::path::Path::new(&path).exists() {
-                tracing::info!("从配置文件加载: {}", path);
-                return Self::from_file(path);
-            }
+        config_paths.into_iter().find(|path| path.exists())
+    }
+
+    /// 用户配置文件路径：~/.config/voice-input/config.toml
+    pub fn user_path() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(format!("{}/.config/voice-input/config.toml", home))
+    }
+
+    /// 重新读取配置文件中的文本后处理步骤和语音命令（其余配置需要重启生效）
+    pub fn reload_processing(&mut self) -> crate::utils::Result<()> {
+        let config = Self::load()?;
+        crate::processing::Pipeline::from_config(&config.processing)?;
+        crate::mode::command::CommandSet::new(&config.commands, config.command.threshold)?;
+        self.processing = config.processing;
+        self.command = config.command;
+        self.commands = config.commands;
+        Ok(())
+    }
+
+    /// 用户词表路径（与用户配置文件同目录）
+    pub fn vocabulary_path() -> PathBuf {
+        Self::user_path().with_file_name("vocabulary.txt")
+    }
+
+    /// 写入带注释的示例配置（config.toml.example）
+    pub fn save_example(path: impl Into<PathBuf>) -> crate::utils::Result<()> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
 
-        tracing::info!("未找到配置文件，使用默认配置");
-        Ok(Self::default())
+        std::fs::write(&path, EXAMPLE)?;
+        Ok(())
     }
 
     /// 保存配置到文件
@@ -177,10 +828,19 @@ impl Config {
     }
 }
 
+/// 随程序打包的示例配置
+const EXAMPLE: &str = include_str!("../../config.toml.example");
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_example_config() {
+        let config: Config = toml::from_str(EXAMPLE).unwrap();
+        assert!(!config.xfyun.is_configured());
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
@@ -197,5 +857,113 @@ mod tests {
 
         let parsed: Config = toml::from_str(&toml_str).unwrap();
         assert_eq!(parsed.audio.sample_rate, config.audio.sample_rate);
+        assert_eq!(parsed.processing.stages, config.processing.stages);
+    }
+
+    #[test]
+    fn test_processing_stages() {
+        let config: Config = toml::from_str(
+            r#"
+            [[processing.stages]]
+            type = "trim"
+
+            [[processing.stages]]
+            type = "strip_trailing_period"
+            enabled = false
+            max_chars = 4
+
+            [[processing.stages]]
+            type = "spoken_punctuation"
+
+            [processing.stages.commands]
+            "艾特" = "@"
+            "#,
+        )
+        .unwrap();
+
+        let stages = &config.processing.stages;
+        assert_eq!(stages.len(), 3);
+        assert!(stages[0].enabled);
+        assert_eq!(stages[0].stage, Stage::Trim);
+        assert!(!stages[1].enabled);
+        assert_eq!(stages[1].stage, Stage::StripTrailingPeriod { max_chars: 4 });
+        match &stages[2].stage {
+            Stage::SpokenPunctuation { builtin, commands, escape } => {
+                assert!(builtin);
+                assert_eq!(commands["艾特"], "@");
+                assert_eq!(escape, &default_spoken_escape());
+            }
+            other => panic!("unexpected stage: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_replace_rules() {
+        let config: Config = toml::from_str(
+            r#"
+            [[processing.stages]]
+            type = "replace"
+
+            [[processing.stages.rules]]
+            from = "我的邮箱"
+            to = "zhang.san@example.com"
+
+            [[processing.stages.rules]]
+            from = '(\d+)号楼'
+            to = "$1 栋"
+            regex = true
+
+            [[processing.stages.rules]]
+            from = "签名模板"
+            to = """
+            张三
+            研发部"""
+            "#,
+        )
+        .unwrap();
+
+        match &config.processing.stages[0].stage {
+            Stage::Replace { rules } => {
+                assert_eq!(rules.len(), 3);
+                assert!(!rules[0].regex);
+                assert!(rules[1].regex);
+                assert_eq!(rules[1].from, r"(\d+)号楼");
+                assert!(rules[2].to.contains('\n'));
+            }
+            other => panic!("unexpected stage: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_voice_commands() {
+        let config: Config = toml::from_str(
+            r#"
+            [[commands]]
+            phrases = ["打开终端", "open terminal"]
+            run = "gnome-terminal"
+
+            [[commands]]
+            phrases = ['发布日报(?P<title>.*)']
+            match = "regex"
+            run = "publish-report {title}"
+            dangerous = true
+
+            [[commands]]
+            phrases = ["锁屏"]
+            match = "fuzzy"
+            dbus = { service = "org.gnome.ScreenSaver", path = "/org/gnome/ScreenSaver", interface = "org.gnome.ScreenSaver", method = "SetActive", args = [true] }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.commands.len(), 3);
+        assert_eq!(config.commands[0].matching, CommandMatch::Exact);
+        assert!(!config.commands[0].dangerous);
+        assert_eq!(config.commands[1].matching, CommandMatch::Regex);
+        assert!(config.commands[1].dangerous);
+        let dbus = config.commands[2].dbus.as_ref().unwrap();
+        assert_eq!(dbus.bus, "session");
+        assert_eq!(dbus.args, [DbusArg::Bool(true)]);
+        assert_eq!(config.command.threshold, 0.8);
     }
 }
@@ -0,0 +1,28 @@
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+use super::protocol::{socket_path, DaemonCommand, DaemonResponse};
+use crate::utils::{Result, VoiceInputError};
+
+/// 向守护进程发送一条命令并等待响应
+pub fn send_command(command: DaemonCommand) -> Result<DaemonResponse> {
+    let path = socket_path();
+    let mut stream = UnixStream::connect(&path).map_err(|e| {
+        VoiceInputError::Daemon(format!(
+            "无法连接守护进程 {}: {}（请先运行 voice-input daemon）",
+            path.display(),
+            e
+        ))
+    })?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+    writeln!(stream, "{}", command.as_str())?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+
+    serde_json::from_str(&line)
+        .map_err(|e| VoiceInputError::Daemon(format!("无法解析守护进程响应: {}", e)))
+}
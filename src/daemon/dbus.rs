@@ -0,0 +1,145 @@
+use tokio::sync::{broadcast, mpsc, oneshot};
+use zbus::object_server::SignalEmitter;
+use zbus::{fdo, interface, Connection};
+
+use super::protocol::{DaemonCommand, DaemonEvent, DaemonResponse};
+use super::server::ControlRequest;
+use crate::utils::{Result, VoiceInputError};
+
+/// D-Bus 服务名
+pub const BUS_NAME: &str = "io.github.MuyaoWorkshop.VoiceInput";
+/// D-Bus 对象路径
+pub const OBJECT_PATH: &str = "/io/github/MuyaoWorkshop/VoiceInput";
+
+/// D-Bus 接口：把方法调用转发给守护进程主循环
+struct VoiceInputService {
+    control: mpsc::UnboundedSender<ControlRequest>,
+}
+
+impl VoiceInputService {
+    async fn request(&self, command: DaemonCommand) -> fdo::Result<DaemonResponse> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.control
+            .send(ControlRequest::Command(command, reply_tx))
+            .map_err(|_| fdo::Error::Failed("守护进程已退出".to_string()))?;
+
+        reply_rx
+            .await
+            .map_err(|_| fdo::Error::Failed("守护进程未响应".to_string()))
+    }
+
+    async fn call(&self, command: DaemonCommand) -> fdo::Result<String> {
+        let response = self.request(command).await?;
+        if response.ok {
+            Ok(response.message)
+        } else {
+            Err(fdo::Error::Failed(response.message))
+        }
+    }
+}
+
+#[interface(name = "io.github.MuyaoWorkshop.VoiceInput1")]
+impl VoiceInputService {
+    /// 开始录音
+    async fn start(&self) -> fdo::Result<String> {
+        self.call(DaemonCommand::Start).await
+    }
+
+    /// 结束录音并输出识别结果
+    async fn stop(&self) -> fdo::Result<String> {
+        self.call(DaemonCommand::Stop).await
+    }
+
+    /// 取消录音，丢弃识别结果
+    async fn cancel(&self) -> fdo::Result<String> {
+        self.call(DaemonCommand::Cancel).await
+    }
+
+    /// 空闲时开始录音，录音中则结束录音
+    async fn toggle(&self) -> fdo::Result<String> {
+        self.call(DaemonCommand::Toggle).await
+    }
+
+    /// 当前状态：idle | connecting | recording | recognizing
+    async fn status(&self) -> fdo::Result<String> {
+        let response = self.request(DaemonCommand::Status).await?;
+        Ok(response.state.as_str().to_string())
+    }
+
+    /// 重新读取配置文件中的文本后处理规则
+    async fn reload(&self) -> fdo::Result<String> {
+        self.call(DaemonCommand::Reload).await
+    }
+
+    /// 状态变化
+    #[zbus(signal)]
+    async fn state_changed(emitter: &SignalEmitter<'_>, state: &str) -> zbus::Result<()>;
+
+    /// 识别中间结果（当前完整文本）
+    #[zbus(signal)]
+    async fn partial_result(emitter: &SignalEmitter<'_>, text: &str) -> zbus::Result<()>;
+
+    /// 最终识别结果
+    #[zbus(signal)]
+    async fn final_result(emitter: &SignalEmitter<'_>, text: &str) -> zbus::Result<()>;
+
+    /// 会话出错
+    #[zbus(signal)]
+    async fn error(emitter: &SignalEmitter<'_>, message: &str) -> zbus::Result<()>;
+}
+
+/// 在会话总线上注册服务，并把守护进程事件转为 D-Bus 信号
+pub async fn serve_dbus(
+    control: mpsc::UnboundedSender<ControlRequest>,
+    mut events: broadcast::Receiver<DaemonEvent>,
+) -> Result<Connection> {
+    let connection = zbus::connection::Builder::session()
+        .and_then(|builder| builder.name(BUS_NAME))
+        .and_then(|builder| builder.serve_at(OBJECT_PATH, VoiceInputService { control }))
+        .map_err(dbus_error)?
+        .build()
+        .await
+        .map_err(dbus_error)?;
+
+    tracing::info!("D-Bus 服务已注册: {}", BUS_NAME);
+
+    let emitter = SignalEmitter::new(&connection, OBJECT_PATH)
+        .map_err(dbus_error)?
+        .into_owned();
+    tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("D-Bus 信号积压，跳过 {} 条事件", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            let sent = match &event {
+                // 音量变化过于频繁，不转为 D-Bus 信号
+                DaemonEvent::Level(_) => continue,
+                DaemonEvent::StateChanged(state) => {
+                    VoiceInputService::state_changed(&emitter, state.as_str()).await
+                }
+                DaemonEvent::PartialResult(text) => {
+                    VoiceInputService::partial_result(&emitter, text).await
+                }
+                DaemonEvent::FinalResult(text) => {
+                    VoiceInputService::final_result(&emitter, text).await
+                }
+                DaemonEvent::Error(message) => VoiceInputService::error(&emitter, message).await,
+            };
+            if let Err(e) = sent {
+                tracing::warn!("发送 D-Bus 信号失败: {}", e);
+            }
+        }
+    });
+
+    Ok(connection)
+}
+
+fn dbus_error(e: zbus::Error) -> VoiceInputError {
+    VoiceInputError::Daemon(format!("D-Bus: {}", e))
+}
@@ -0,0 +1,9 @@
+pub mod client;
+pub mod dbus;
+pub mod protocol;
+pub mod server;
+pub mod tray;
+
+pub use client::send_command;
+pub use protocol::{socket_path, DaemonCommand, DaemonEvent, DaemonResponse, DaemonState};
+pub use server::{run_daemon, ControlRequest, DaemonOptions};
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::utils::VoiceInputError;
+
+/// 守护进程控制命令
+///
+/// 协议为按行收发的文本：客户端发送一行命令名，
+/// 守护进程回复一行 JSON 格式的 [`DaemonResponse`]。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DaemonCommand {
+    /// 空闲时开始录音，录音中则结束录音
+    Toggle,
+    /// 开始录音
+    Start,
+    /// 结束录音并输出识别结果
+    Stop,
+    /// 取消录音，丢弃识别结果
+    Cancel,
+    /// 查询当前状态
+    Status,
+    /// 重新读取配置文件中的文本后处理规则
+    Reload,
+}
+
+impl DaemonCommand {
+    /// 协议中的命令名
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Cancel => "cancel",
+            Self::Status => "status",
+            Self::Reload => "reload",
+        }
+    }
+}
+
+impl FromStr for DaemonCommand {
+    type Err = VoiceInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "toggle" => Ok(Self::Toggle),
+            "start" => Ok(Self::Start),
+            "stop" => Ok(Self::Stop),
+            "cancel" => Ok(Self::Cancel),
+            "status" => Ok(Self::Status),
+            "reload" => Ok(Self::Reload),
+            other => Err(VoiceInputError::Daemon(format!("未知命令: {}", other))),
+        }
+    }
+}
+
+/// 守护进程状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DaemonState {
+    /// 空闲
+    Idle,
+    /// 正在连接识别服务
+    Connecting,
+    /// 录音中
+    Recording,
+    /// 已停止录音，等待最终识别结果
+    Recognizing,
+}
+
+impl DaemonState {
+    /// 状态名（与 JSON / D-Bus 中使用的名称一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Connecting => "connecting",
+            Self::Recording => "recording",
+            Self::Recognizing => "recognizing",
+        }
+    }
+}
+
+/// 守护进程向外广播的事件（D-Bus 信号等）
+#[derive(Debug, Clone, PartialEq)]
+pub enum DaemonEvent {
+    /// 状态变化
+    StateChanged(DaemonState),
+    /// 录音音量（0-100）
+    Level(u8),
+    /// 识别中间结果（当前完整文本）
+    PartialResult(String),
+    /// 最终识别结果
+    FinalResult(String),
+    /// 会话出错（用户友好的错误信息）
+    Error(String),
+}
+
+/// 守护进程响应
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonResponse {
+    /// 命令是否执行成功
+    pub ok: bool,
+    /// 执行命令后的状态
+    pub state: DaemonState,
+    /// 提示信息
+    pub message: String,
+    /// 最近一次识别结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_result: Option<String>,
+}
+
+/// 控制套接字路径
+///
+/// 优先使用 `$XDG_RUNTIME_DIR/voice-input.sock`，
+/// 未设置时退回到 `/tmp/voice-input-$USER/voice-input.sock`（守护进程把目录权限设为 0700）。
+pub fn socket_path() -> PathBuf {
+    let dir = match std::env::var("XDG_RUNTIME_DIR") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            let user = std::env::var("USER").unwrap_or_else(|_| "default".to_string());
+            PathBuf::from(format!("/tmp/voice-input-{}", user))
+        }
+    };
+    dir.join("voice-input.sock")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_roundtrip() {
+        for command in [
+            DaemonCommand::Toggle,
+            DaemonCommand::Start,
+            DaemonCommand::Stop,
+            DaemonCommand::Cancel,
+            DaemonCommand::Status,
+            DaemonCommand::Reload,
+        ] {
+            assert_eq!(command.as_str().parse::<DaemonCommand>().unwrap(), command);
+        }
+        assert_eq!(" STOP\n".parse::<DaemonCommand>().unwrap(), DaemonCommand::Stop);
+        assert!("pause".parse::<DaemonCommand>().is_err());
+    }
+
+    #[test]
+    fn test_response_json() {
+        let response = DaemonResponse {
+            ok: true,
+            state: DaemonState::Recording,
+            message: "开始录音".to_string(),
+            last_result: None,
+        };
+        let line = serde_json::to_string(&response).unwrap();
+        assert!(line.contains("\"state\":\"recording\""));
+        assert!(!line.contains("last_result"));
+
+        let parsed: DaemonResponse = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed, response);
+    }
+}
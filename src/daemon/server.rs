@@ -0,0 +1,439 @@
+use std::cell::RefCell;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::{JoinHandle, LocalSet};
+
+use super::dbus::serve_dbus;
+use super::protocol::{socket_path, DaemonCommand, DaemonEvent, DaemonResponse, DaemonState};
+use super::tray::spawn_tray;
+use crate::input::run_hotkeys;
+use crate::mode::{Mode, Revisions};
+use crate::online::RecognitionEvent;
+use crate::output::DesktopNotifier;
+use crate::session::{connect_notifier, run_session, SessionOptions};
+use crate::utils::{Result, VoiceInputError};
+use crate::Config;
+
+/// 来自其他线程（D-Bus 服务、快捷键、托盘图标）的控制请求
+pub enum ControlRequest {
+    /// 执行控制命令，通过 oneshot 返回响应
+    Command(DaemonCommand, oneshot::Sender<DaemonResponse>),
+    /// 切换识别语言和方言（对之后开始的会话生效）
+    SetLanguage { language: String, accent: String },
+    /// 退出守护进程
+    Quit,
+}
+
+/// 守护进程运行选项
+#[derive(Debug, Clone, Default)]
+pub struct DaemonOptions {
+    /// 流式输出
+    pub streaming: bool,
+    /// 显示系统托盘图标
+    pub tray: bool,
+    /// 听写模式
+    pub mode: Mode,
+}
+
+/// 正在进行的听写会话
+struct ActiveSession {
+    id: u64,
+    running: Arc<AtomicBool>,
+    /// 是否已连接识别服务并开始录音
+    recording: bool,
+    handle: JoinHandle<()>,
+}
+
+impl ActiveSession {
+    /// 停止录音并中止会话任务
+    ///
+    /// 先清除运行标志，让录音和识别连接自行结束，再中止任务本身。
+    fn cancel(self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.handle.abort();
+    }
+}
+
+/// 创建只有当前用户能访问的目录（0700）；已存在时检查属主和权限
+fn create_private_dir(dir: &Path) -> Result<()> {
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e.into()),
+    }
+    let metadata = std::fs::symlink_metadata(dir)?;
+    let uid = std::fs::metadata("/proc/self")?.uid();
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+        return Err(VoiceInputError::Daemon(format!(
+            "{} 不是当前用户的私有目录（需要权限 0700）",
+            dir.display()
+        )));
+    }
+    Ok(())
+}
+
+/// 守护进程状态
+struct Daemon {
+    config: Config,
+    streaming: bool,
+    mode: Mode,
+    active: Option<ActiveSession>,
+    next_id: u64,
+    last_result: Option<String>,
+    /// 最近的结果，供语音编辑命令修改
+    revisions: Arc<Mutex<Revisions>>,
+    /// 各次会话共用的桌面通知
+    notifier: Option<DesktopNotifier>,
+    events: broadcast::Sender<DaemonEvent>,
+}
+
+type SharedDaemon = Rc<RefCell<Daemon>>;
+
+/// 以守护进程方式运行
+///
+/// 监听 [`socket_path`] 上的控制命令，直到收到 SIGINT / SIGTERM。
+/// 收到 SIGHUP 或 `reload` 命令时重新读取文本后处理规则和语音命令。
+/// 录音流不能跨线程移动，所以会话都在同一个 `LocalSet` 中运行。
+pub async fn run_daemon(config: Config, options: DaemonOptions) -> Result<()> {
+    let path = socket_path();
+    // 套接字放在只有当前用户能访问的目录中，绑定和设置权限之间其他用户也无法连接
+    if let Some(dir) = path.parent() {
+        create_private_dir(dir)?;
+    }
+
+    if path.exists() {
+        if UnixStream::connect(&path).await.is_ok() {
+            return Err(VoiceInputError::Daemon(format!(
+                "守护进程已在运行: {}",
+                path.display()
+            )));
+        }
+        // 上次异常退出遗留的套接字文件
+        std::fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    tracing::info!("守护进程已启动，监听: {}", path.display());
+    println!("🎧 守护进程已启动: {}", path.display());
+
+    let (events, _) = broadcast::channel(64);
+    let (control_tx, mut control_rx) = mpsc::unbounded_channel::<ControlRequest>();
+
+    // 全局快捷键（在守护进程内监听）
+    if config.hotkey.enabled {
+        let (hotkey_tx, mut hotkey_rx) = mpsc::unbounded_channel();
+        let hotkey_config = config.hotkey.clone();
+        tokio::spawn(async move {
+            if let Err(e) = run_hotkeys(&hotkey_config, hotkey_tx).await {
+                tracing::error!("全局快捷键监听失败: {}", e.user_message());
+            }
+        });
+
+        let control_tx = control_tx.clone();
+        tokio::spawn(async move {
+            while let Some(command) = hotkey_rx.recv().await {
+                let (reply_tx, _) = oneshot::channel();
+                if control_tx.send(ControlRequest::Command(command, reply_tx)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    // 系统托盘图标
+    let _tray = if options.tray {
+        match spawn_tray(control_tx.clone(), events.subscribe(), &config).await {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                tracing::warn!("托盘图标启动失败: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // D-Bus 服务（连接失败不影响套接字控制）
+    let _dbus = if config.daemon.dbus {
+        match serve_dbus(control_tx, events.subscribe()).await {
+            Ok(connection) => Some(connection),
+            Err(e) => {
+                tracing::warn!("D-Bus 服务启动失败，仅使用套接字控制: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let notifier = connect_notifier(&config).await;
+    let daemon = Rc::new(RefCell::new(Daemon {
+        config,
+        streaming: options.streaming,
+        mode: options.mode,
+        active: None,
+        next_id: 0,
+        last_result: None,
+        revisions: Arc::default(),
+        notifier,
+        events,
+    }));
+
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let mut sighup = signal(SignalKind::hangup())?;
+    let local = LocalSet::new();
+    let result = local
+        .run_until(async {
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        match accepted {
+                            Ok((stream, _)) => {
+                                tokio::task::spawn_local(handle_connection(daemon.clone(), stream));
+                            }
+                            Err(e) => tracing::warn!("接受连接失败: {}", e),
+                        }
+                    }
+                    Some(request) = control_rx.recv() => {
+                        match request {
+                            ControlRequest::Command(command, reply) => {
+                                reply.send(execute(&daemon, command)).ok();
+                            }
+                            ControlRequest::SetLanguage { language, accent } => {
+                                tracing::info!("切换识别语言: {} / {}", language, accent);
+                                let mut inner = daemon.borrow_mut();
+                                inner.config.xfyun.language = language;
+                                inner.config.xfyun.accent = accent;
+                            }
+                            ControlRequest::Quit => break,
+                        }
+                    }
+                    _ = tokio::signal::ctrl_c() => break,
+                    _ = sigterm.recv() => break,
+                    _ = sighup.recv() => {
+                        daemon.borrow_mut().reload();
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await;
+
+    if let Some(active) = daemon.borrow_mut().active.take() {
+        active.cancel();
+    }
+    std::fs::remove_file(&path).ok();
+    tracing::info!("守护进程已退出");
+
+    result
+}
+
+/// 处理一个客户端连接（每行一条命令）
+async fn handle_connection(daemon: SharedDaemon, stream: UnixStream) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match line.parse::<DaemonCommand>() {
+            Ok(command) => execute(&daemon, command),
+            Err(e) => {
+                let daemon = daemon.borrow();
+                daemon.respond(false, e.to_string())
+            }
+        };
+
+        let mut payload = serde_json::to_string(&response).unwrap_or_default();
+        payload.push('\n');
+        if writer.write_all(payload.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// 执行控制命令
+fn execute(daemon: &SharedDaemon, command: DaemonCommand) -> DaemonResponse {
+    tracing::info!("收到命令: {}", command.as_str());
+
+    match command {
+        DaemonCommand::Start => start(daemon),
+        DaemonCommand::Stop => stop(daemon),
+        DaemonCommand::Toggle => {
+            let state = daemon.borrow().state();
+            match state {
+                DaemonState::Idle => start(daemon),
+                DaemonState::Connecting | DaemonState::Recording => stop(daemon),
+                DaemonState::Recognizing => daemon
+                    .borrow()
+                    .respond(false, "正在识别，请稍候".to_string()),
+            }
+        }
+        DaemonCommand::Cancel => {
+            let mut inner = daemon.borrow_mut();
+            match inner.active.take() {
+                Some(active) => {
+                    tracing::info!("已取消会话 #{}", active.id);
+                    active.cancel();
+                    inner.emit_state();
+                    inner.respond(true, "已取消录音".to_string())
+                }
+                None => inner.respond(false, "当前没有录音".to_string()),
+            }
+        }
+        DaemonCommand::Status => {
+            let inner = daemon.borrow();
+            let message = match inner.state() {
+                DaemonState::Idle => "空闲",
+                DaemonState::Connecting => "正在连接",
+                DaemonState::Recording => "录音中",
+                DaemonState::Recognizing => "识别中",
+            };
+            inner.respond(true, message.to_string())
+        }
+        DaemonCommand::Reload => daemon.borrow_mut().reload(),
+    }
+}
+
+/// 开始新的听写会话
+fn start(daemon: &SharedDaemon) -> DaemonResponse {
+    let mut inner = daemon.borrow_mut();
+    if inner.active.is_some() {
+        return inner.respond(false, "已经在录音".to_string());
+    }
+
+    inner.next_id += 1;
+    let id = inner.next_id;
+    let running = Arc::new(AtomicBool::new(true));
+
+    // 识别事件 → 守护进程事件
+    let (session_tx, mut session_rx) = mpsc::unbounded_channel();
+    let daemon_for_events = daemon.clone();
+    tokio::task::spawn_local(async move {
+        while let Some(event) = session_rx.recv().await {
+            let mut inner = daemon_for_events.borrow_mut();
+            match event {
+                RecognitionEvent::Recording => {
+                    if let Some(active) = inner.active.as_mut().filter(|a| a.id == id) {
+                        active.recording = true;
+                    }
+                    inner.emit_state();
+                }
+                RecognitionEvent::Level(level) => {
+                    inner.events.send(DaemonEvent::Level(level)).ok();
+                }
+                RecognitionEvent::Partial { text, .. } => {
+                    inner.events.send(DaemonEvent::PartialResult(text)).ok();
+                }
+            }
+        }
+    });
+
+    let options = SessionOptions {
+        streaming: inner.streaming,
+        mode: inner.mode,
+        running: Some(running.clone()),
+        events: Some(session_tx),
+        revisions: Some(inner.revisions.clone()),
+        notifier: inner.notifier.clone(),
+        ..Default::default()
+    };
+    let config = inner.config.clone();
+
+    let daemon_for_task = daemon.clone();
+    let handle = tokio::task::spawn_local(async move {
+        let result = run_session(&config, options).await;
+
+        let mut inner = daemon_for_task.borrow_mut();
+        if inner.active.as_ref().map(|a| a.id) == Some(id) {
+            inner.active = None;
+        }
+        match result {
+            Ok(transcript) => {
+                let text = transcript.text();
+                tracing::info!("会话 #{} 完成: {}", id, text);
+                inner.events.send(DaemonEvent::FinalResult(text.clone())).ok();
+                inner.last_result = Some(text);
+            }
+            Err(e) => {
+                tracing::error!("会话 #{} 失败: {}", id, e.user_message());
+                inner.events.send(DaemonEvent::Error(e.user_message())).ok();
+            }
+        }
+        inner.emit_state();
+    });
+
+    inner.active = Some(ActiveSession {
+        id,
+        running,
+        recording: false,
+        handle,
+    });
+    tracing::info!("开始会话 #{}", id);
+    inner.emit_state();
+    inner.respond(true, "开始录音".to_string())
+}
+
+/// 结束录音，等待最终识别结果后输出
+fn stop(daemon: &SharedDaemon) -> DaemonResponse {
+    let inner = daemon.borrow();
+    match &inner.active {
+        Some(active) => {
+            active.running.store(false, Ordering::SeqCst);
+            inner.emit_state();
+            inner.respond(true, "正在结束录音".to_string())
+        }
+        None => inner.respond(false, "当前没有录音".to_string()),
+    }
+}
+
+impl Daemon {
+    /// 当前状态
+    fn state(&self) -> DaemonState {
+        match &self.active {
+            None => DaemonState::Idle,
+            Some(active) if !active.running.load(Ordering::SeqCst) => DaemonState::Recognizing,
+            Some(active) if active.recording => DaemonState::Recording,
+            Some(_) => DaemonState::Connecting,
+        }
+    }
+
+    /// 广播当前状态
+    fn emit_state(&self) {
+        self.events.send(DaemonEvent::StateChanged(self.state())).ok();
+    }
+
+    /// 重新加载文本后处理规则和语音命令（对之后开始的会话生效）
+    fn reload(&mut self) -> DaemonResponse {
+        match self.config.reload_processing() {
+            Ok(()) => {
+                tracing::info!("已重新加载文本后处理规则和语音命令");
+                self.respond(true, "已重新加载文本后处理规则和语音命令".to_string())
+            }
+            Err(e) => {
+                tracing::warn!("重新加载配置失败: {}", e);
+                self.respond(false, e.user_message())
+            }
+        }
+    }
+
+    fn respond(&self, ok: bool, message: String) -> DaemonResponse {
+        DaemonResponse {
+            ok,
+            state: self.state(),
+            message,
+            last_result: self.last_result.clone(),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::process::Command;
+
+use ksni::menu::{RadioGroup, RadioItem, StandardItem, SubMenu};
+use ksni::{Handle, MenuItem, ToolTip, Tray, TrayMethods};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+use super::protocol::{DaemonCommand, DaemonEvent, DaemonState};
+use super::server::ControlRequest;
+use crate::output::ClipboardOutput;
+use crate::utils::{Result, VoiceInputError};
+use crate::Config;
+
+/// 托盘菜单中保留的最近结果数量
+const RECENT_RESULTS: usize = 5;
+
+/// 可切换的识别语言：（菜单名, language, accent）
+const LANGUAGES: [(&str, &str, &str); 3] = [
+    ("普通话", "zh_cn", "mandarin"),
+    ("粤语", "zh_cn", "cantonese"),
+    ("英语", "en_us", "mandarin"),
+];
+
+/// 系统托盘图标（StatusNotifierItem）
+///
+/// 目前只有讯飞云一个识别引擎，菜单中只提供语言切换，不提供引擎切换。
+pub struct VoiceInputTray {
+    state: DaemonState,
+    /// 当前音量（0-100）
+    level: u8,
+    /// 当前语言在 [`LANGUAGES`] 中的序号
+    language: usize,
+    recent: VecDeque<String>,
+    control: mpsc::UnboundedSender<ControlRequest>,
+}
+
+impl VoiceInputTray {
+    fn send(&self, command: DaemonCommand) {
+        let (reply_tx, _) = oneshot::channel();
+        self.control
+            .send(ControlRequest::Command(command, reply_tx))
+            .ok();
+    }
+
+    fn state_label(&self) -> String {
+        match self.state {
+            DaemonState::Idle => "空闲".to_string(),
+            DaemonState::Connecting => "正在连接…".to_string(),
+            DaemonState::Recording => format!("录音中 · 音量 {}%", self.level),
+            DaemonState::Recognizing => "识别中…".to_string(),
+        }
+    }
+}
+
+impl Tray for VoiceInputTray {
+    fn id(&self) -> String {
+        "voice-input".to_string()
+    }
+
+    fn title(&self) -> String {
+        format!("语音输入 - {}", self.state_label())
+    }
+
+    fn icon_name(&self) -> String {
+        match self.state {
+            DaemonState::Idle => "audio-input-microphone",
+            DaemonState::Connecting => "network-transmit-receive",
+            DaemonState::Recording => match self.level {
+                0..=4 => "microphone-sensitivity-low",
+                5..=14 => "microphone-sensitivity-medium",
+                _ => "microphone-sensitivity-high",
+            },
+            DaemonState::Recognizing => "view-refresh",
+        }
+        .to_string()
+    }
+
+    fn tool_tip(&self) -> ToolTip {
+        ToolTip {
+            title: "语音输入".to_string(),
+            description: self.state_label(),
+            ..Default::default()
+        }
+    }
+
+    /// 左键单击：开始 / 结束录音
+    fn activate(&mut self, _x: i32, _y: i32) {
+        self.send(DaemonCommand::Toggle);
+    }
+
+    fn menu(&self) -> Vec<MenuItem<Self>> {
+        let idle = self.state == DaemonState::Idle;
+
+        let recent: Vec<MenuItem<Self>> = self
+            .recent
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                StandardItem {
+                    label: menu_preview(text),
+                    activate: Box::new(move |tray: &mut Self| {
+                        if let Some(text) = tray.recent.get(index).cloned() {
+                            // 菜单回调中不要阻塞
+                            std::thread::spawn(move || {
+                                if let Err(e) =
+                                    ClipboardOutput::new().and_then(|mut c| c.copy(&text))
+                                {
+                                    tracing::warn!("{}", e);
+                                }
+                            });
+                        }
+                    }),
+                    ..Default::default()
+                }
+                .into()
+            })
+            .collect();
+
+        vec![
+            StandardItem {
+                label: self.state_label(),
+                enabled: false,
+                ..Default::default()
+            }
+            .into(),
+            MenuItem::Separator,
+            StandardItem {
+                label: if idle { "开始录音" } else { "结束录音" }.to_string(),
+                icon_name: if idle {
+                    "media-record"
+                } else {
+                    "media-playback-stop"
+                }
+                .to_string(),
+                activate: Box::new(|tray: &mut Self| tray.send(DaemonCommand::Toggle)),
+                ..Default::default()
+            }
+            .into(),
+            StandardItem {
+                label: "取消录音".to_string(),
+                enabled: !idle,
+                activate: Box::new(|tray: &mut Self| tray.send(DaemonCommand::Cancel)),
+                ..Default::default()
+            }
+            .into(),
+            MenuItem::Separator,
+            SubMenu {
+                label: "识别语言".to_string(),
+                submenu: vec![RadioGroup {
+                    selected: self.language,
+                    select: Box::new(|tray: &mut Self, index| {
+                        let (_, language, accent) = LANGUAGES[index];
+                        tray.language = index;
+                        tray.control
+                            .send(ControlRequest::SetLanguage {
+                                language: language.to_string(),
+                                accent: accent.to_string(),
+                            })
+                            .ok();
+                    }),
+                    options: LANGUAGES
+                        .iter()
+                        .map(|(label, _, _)| RadioItem {
+                            label: label.to_string(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                }
+                .into()],
+                ..Default::default()
+            }
+            .into(),
+            SubMenu {
+                label: "最近结果".to_string(),
+                enabled: !recent.is_empty(),
+                submenu: recent,
+                ..Default::default()
+            }
+            .into(),
+            MenuItem::Separator,
+            StandardItem {
+                label: "打开配置文件".to_string(),
+                icon_name: "document-properties".to_string(),
+                activate: Box::new(|_: &mut Self| open_config()),
+                ..Default::default()
+            }
+            .into(),
+            StandardItem {
+                label: "退出".to_string(),
+                icon_name: "application-exit".to_string(),
+                activate: Box::new(|tray: &mut Self| {
+                    tray.control.send(ControlRequest::Quit).ok();
+                }),
+                ..Default::default()
+            }
+            .into(),
+        ]
+    }
+}
+
+/// 显示托盘图标，并根据守护进程事件更新状态
+pub async fn spawn_tray(
+    control: mpsc::UnboundedSender<ControlRequest>,
+    mut events: broadcast::Receiver<DaemonEvent>,
+    config: &Config,
+) -> Result<Handle<VoiceInputTray>> {
+    let language = LANGUAGES
+        .iter()
+        .position(|(_, language, accent)| {
+            *language == config.xfyun.language
+                && (*language != "zh_cn" || *accent == config.xfyun.accent)
+        })
+        .unwrap_or(0);
+
+    let tray = VoiceInputTray {
+        state: DaemonState::Idle,
+        level: 0,
+        language,
+        recent: VecDeque::with_capacity(RECENT_RESULTS),
+        control,
+    };
+    let handle = tray
+        .spawn()
+        .await
+        .map_err(|e| VoiceInputError::Daemon(format!("托盘图标: {}", e)))?;
+
+    let updater = handle.clone();
+    tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            let updated = updater
+                .update(|tray| match event {
+                    DaemonEvent::StateChanged(state) => {
+                        tray.state = state;
+                        tray.level = 0;
+                    }
+                    DaemonEvent::Level(level) => tray.level = level,
+                    DaemonEvent::FinalResult(text) if !text.is_empty() => {
+                        if tray.recent.len() == RECENT_RESULTS {
+                            tray.recent.pop_back();
+                        }
+                        tray.recent.push_front(text);
+                    }
+                    _ => {}
+                })
+                .await;
+            if updated.is_none() {
+                break;
+            }
+        }
+    });
+
+    Ok(handle)
+}
+
+/// 用默认程序打开配置文件（不存在时先写入带注释的示例配置）
+fn open_config() {
+    let path: PathBuf = Config::find_path().unwrap_or_else(|| {
+        let path = Config::user_path();
+        if let Err(e) = Config::save_example(&path) {
+            tracing::warn!("创建配置文件失败: {}", e);
+        }
+        path
+    });
+
+    if let Err(e) = Command::new("xdg-open").arg(&path).spawn() {
+        tracing::warn!("打开配置文件失败: {}", e);
+    }
+}
+
+/// 菜单中显示的结果预览
+fn menu_preview(text: &str) -> String {
+    let preview: String = text.chars().take(24).collect();
+    if preview.chars().count() < text.chars().count() {
+        format!("{}…", preview)
+    } else {
+        preview
+    }
+}
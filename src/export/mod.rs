@@ -0,0 +1,238 @@
+use std::fmt::Write;
+
+use crate::history::HistoryEntry;
+use crate::transcript::Segment;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// 结构化数据（含分段时间）
+    Json,
+    /// 会议记录
+    Markdown,
+    /// SubRip 字幕
+    Srt,
+    /// WebVTT 字幕
+    Vtt,
+}
+
+/// 把若干次听写会话渲染为指定格式
+///
+/// 会话按传入顺序排列。字幕格式中后一次会话的时间接在前一次之后，
+/// 保证时间轴连续不重叠。
+pub fn render(entries: &[HistoryEntry], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => render_json(entries),
+        ExportFormat::Markdown => render_markdown(entries),
+        ExportFormat::Srt => render_subtitles(entries, ',', false),
+        ExportFormat::Vtt => render_subtitles(entries, '.', true),
+    }
+}
+
+fn render_json(entries: &[HistoryEntry]) -> String {
+    let sessions: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "id": entry.id,
+                "created_at": entry.created_at.to_rfc3339(),
+                "engine": entry.engine,
+                "language": entry.language,
+                "duration": entry.duration,
+                "output": entry.output,
+                "text": entry.text,
+                "segments": entry.timed_segments(),
+            })
+        })
+        .collect();
+
+    let mut output = serde_json::to_string_pretty(&sessions).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
+fn render_markdown(entries: &[HistoryEntry]) -> String {
+    let mut output = String::from("# 听写记录\n");
+
+    for entry in entries {
+        writeln!(output, "\n## {}\n", entry.created_at.format("%Y-%m-%d %H:%M:%S")).ok();
+        writeln!(
+            output,
+            "> 记录 #{} · {} · {} · {:.1} 秒\n",
+            entry.id, entry.engine, entry.language, entry.duration
+        )
+        .ok();
+        for segment in entry.timed_segments() {
+            writeln!(
+                output,
+                "- `{} – {}` {}",
+                format_clock(segment.start_ms),
+                format_clock(segment.end_ms),
+                segment.text
+            )
+            .ok();
+        }
+    }
+
+    output
+}
+
+/// SRT 与 WebVTT 只有文件头和毫秒分隔符不同
+fn render_subtitles(entries: &[HistoryEntry], separator: char, vtt: bool) -> String {
+    let mut output = String::new();
+    if vtt {
+        output.push_str("WEBVTT\n");
+    }
+
+    let mut offset = 0;
+    let mut index = 0;
+    for entry in entries {
+        let segments = entry.timed_segments();
+        for segment in &segments {
+            index += 1;
+            if vtt {
+                output.push('\n');
+            } else {
+                if index > 1 {
+                    output.push('\n');
+                }
+                writeln!(output, "{}", index).ok();
+            }
+            writeln!(
+                output,
+                "{} --> {}",
+                format_timestamp(offset + segment.start_ms, separator),
+                format_timestamp(offset + segment.end_ms, separator)
+            )
+            .ok();
+            writeln!(output, "{}", segment.text).ok();
+        }
+        offset += session_length(entry, &segments);
+    }
+
+    output
+}
+
+/// 会话在时间轴上占用的长度（毫秒）
+fn session_length(entry: &HistoryEntry, segments: &[Segment]) -> u64 {
+    let last_end = segments.iter().map(|s| s.end_ms).max().unwrap_or(0);
+    last_end.max((entry.duration * 1000.0) as u64)
+}
+
+/// `HH:MM:SS,mmm`（SRT）或 `HH:MM:SS.mmm`（WebVTT）
+fn format_timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// Markdown 中使用的 `MM:SS.s`
+fn format_clock(ms: u64) -> String {
+    format!("{:02}:{:02}.{}", ms / 60_000, ms / 1000 % 60, ms % 1000 / 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    fn entry(id: i64, duration: f64, segments: &[(u64, u64, &str)]) -> HistoryEntry {
+        let segments: Vec<Segment> = segments
+            .iter()
+            .map(|&(start_ms, end_ms, text)| Segment {
+                start_ms,
+                end_ms,
+                text: text.to_string(),
+                words: Vec::new(),
+            })
+            .collect();
+        HistoryEntry {
+            id,
+            created_at: Local.with_ymd_and_hms(2026, 10, 18, 9, 30, 0).unwrap(),
+            engine: "xfyun".to_string(),
+            language: "zh_cn".to_string(),
+            duration,
+            output: "clipboard".to_string(),
+            text: segments.iter().map(|s| s.text.as_str()).collect(),
+            segments,
+            revision_of: None,
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3_723_045, '.'), "01:02:03.045");
+        assert_eq!(format_clock(75_250), "01:15.2");
+    }
+
+    #[test]
+    fn test_render_srt() {
+        let entries = [
+            entry(1, 4.0, &[(100, 1500, "大家好"), (1800, 3200, "开始开会")]),
+            entry(2, 2.0, &[(200, 1000, "散会")]),
+        ];
+        let srt = render(&entries, ExportFormat::Srt);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,100 --> 00:00:01,500\n大家好\n\n\
+             2\n00:00:01,800 --> 00:00:03,200\n开始开会\n\n\
+             3\n00:00:04,200 --> 00:00:05,000\n散会\n"
+        );
+    }
+
+    #[test]
+    fn test_render_vtt() {
+        let vtt = render(&[entry(1, 2.0, &[(0, 1500, "你好")])], ExportFormat::Vtt);
+        assert_eq!(vtt, "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\n你好\n");
+    }
+
+    #[test]
+    fn test_render_markdown_and_json() {
+        let entries = [entry(7, 3.0, &[(0, 1500, "第一句"), (1500, 3000, "第二句")])];
+
+        let markdown = render(&entries, ExportFormat::Markdown);
+        assert!(markdown.contains("## 2026-10-18 09:30:00"));
+        assert!(markdown.contains("- `00:01.5 – 00:03.0` 第二句"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&entries, ExportFormat::Json)).unwrap();
+        assert_eq!(json[0]["id"], 7);
+        assert_eq!(json[0]["text"], "第一句第二句");
+        assert_eq!(json[0]["segments"][1]["start_ms"], 1500);
+    }
+
+    #[test]
+    fn test_entry_without_segments() {
+        let mut old = entry(1, 2.5, &[]);
+        old.text = "旧记录".to_string();
+        let srt = render(&[old], ExportFormat::Srt);
+        assert_eq!(srt, "1\n00:00:00,000 --> 00:00:02,500\n旧记录\n");
+    }
+
+    #[test]
+    fn test_processed_text_in_cues() {
+        // 分段是原文，保存的文本经过了后处理：字幕显示处理后的文本，每条保留自己的时间
+        let mut processed = entry(1, 3.0, &[(100, 1200, "二十三个人"), (1500, 2600, "来了")]);
+        processed.text = "23个人，来了。".to_string();
+
+        let srt = render(&[processed.clone()], ExportFormat::Srt);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,100 --> 00:00:01,200\n23个人，\n\n\
+             2\n00:00:01,500 --> 00:00:02,600\n来了。\n"
+        );
+
+        let vtt = render(&[processed.clone()], ExportFormat::Vtt);
+        assert!(vtt.ends_with("00:00:01.500 --> 00:00:02.600\n来了。\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&[processed], ExportFormat::Json)).unwrap();
+        assert_eq!(json[0]["segments"][0]["text"], "23个人，");
+    }
+}
@@ -0,0 +1,345 @@
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::transcript::{align_segments, Segment};
+use crate::utils::{Result, VoiceInputError};
+
+/// 一条听写历史记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// 记录 ID（写入前为 0）
+    pub id: i64,
+    /// 识别完成时间
+    pub created_at: DateTime<Local>,
+    /// 识别引擎，如 xfyun
+    pub engine: String,
+    /// 识别语言，如 zh_cn
+    pub language: String,
+    /// 会话时长（秒，含录音和识别）
+    pub duration: f64,
+    /// 输出目标，如 clipboard、clipboard+typing
+    pub output: String,
+    /// 识别结果
+    pub text: String,
+    /// 带起止时间的分段（旧记录为空）
+    pub segments: Vec<Segment>,
+    /// 语音编辑的结果：被修改的原始记录 ID
+    pub revision_of: Option<i64>,
+}
+
+impl HistoryEntry {
+    /// 单行预览（换行替换为空格，超出部分用 … 表示）
+    pub fn preview(&self, max_chars: usize) -> String {
+        let flat = self.text.replace(['\n', '\r'], " ");
+        let mut chars = flat.chars();
+        let head: String = chars.by_ref().take(max_chars).collect();
+        if chars.next().is_some() {
+            format!("{}…", head)
+        } else {
+            head
+        }
+    }
+
+    /// 分段结果
+    ///
+    /// 分段是后处理前的原文（旧版本保存的记录）时，把保存的文本按分段边界重新切开；
+    /// 没有分段信息时把整段文字作为一个分段。
+    pub fn timed_segments(&self) -> Vec<Segment> {
+        let raw: String = self.segments.iter().map(|s| s.text.as_str()).collect();
+        if !self.segments.is_empty() && raw == self.text {
+            return self.segments.clone();
+        }
+        let aligned = align_segments(&self.segments, &self.text);
+        if !aligned.is_empty() {
+            return aligned;
+        }
+        let start_ms = self.segments.first().map_or(0, |s| s.start_ms);
+        let end_ms = match self.segments.last() {
+            Some(segment) => segment.end_ms,
+            None => (self.duration * 1000.0) as u64,
+        };
+        vec![Segment {
+            start_ms,
+            end_ms,
+            text: self.text.clone(),
+            words: Vec::new(),
+        }]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let segments: String = row.get(7)?;
+        let created_at: String = row.get(1)?;
+        let created_at = DateTime::parse_from_rfc3339(&created_at)
+            .map(|t| t.with_timezone(&Local))
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
+            })?;
+
+        Ok(Self {
+            id: row.get(0)?,
+            created_at,
+            engine: row.get(2)?,
+            language: row.get(3)?,
+            duration: row.get(4)?,
+            output: row.get(5)?,
+            text: row.get(6)?,
+            segments: serde_json::from_str(&segments).unwrap_or_default(),
+            revision_of: row.get(8)?,
+        })
+    }
+}
+
+/// 听写历史（SQLite）
+pub struct HistoryStore {
+    connection: Connection,
+}
+
+const COLUMNS: &str = "id, created_at, engine, language, duration, output, text, segments, revision_of";
+
+impl HistoryStore {
+    /// 打开（或创建）历史数据库
+    ///
+    /// 听写内容只允许当前用户读写：新建的目录权限为 0700，数据库文件为 0600。
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::DirBuilder::new().recursive(true).mode(0o700).create(parent)?;
+        }
+        std::fs::OpenOptions::new().create(true).append(true).mode(0o600).open(path)?;
+        // 旧版本创建的数据库文件也收紧权限（SQLite 的日志文件沿用数据库文件的权限）
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+        let connection = Connection::open(path).map_err(history_error)?;
+        Self::init(connection)
+    }
+
+    /// 内存数据库（不落盘）
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory().map_err(history_error)?)
+    }
+
+    fn init(connection: Connection) -> Result<Self> {
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS history (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    created_at TEXT NOT NULL,
+                    engine TEXT NOT NULL,
+                    language TEXT NOT NULL,
+                    duration REAL NOT NULL,
+                    output TEXT NOT NULL,
+                    text TEXT NOT NULL,
+                    segments TEXT NOT NULL DEFAULT '[]',
+                    revision_of INTEGER
+                );
+                CREATE INDEX IF NOT EXISTS history_created_at ON history (created_at);",
+            )
+            .map_err(history_error)?;
+
+        // 旧版本数据库没有分段列
+        if connection.prepare("SELECT segments FROM history LIMIT 0").is_err() {
+            connection
+                .execute_batch("ALTER TABLE history ADD COLUMN segments TEXT NOT NULL DEFAULT '[]'")
+                .map_err(history_error)?;
+        }
+        if connection.prepare("SELECT revision_of FROM history LIMIT 0").is_err() {
+            connection
+                .execute_batch("ALTER TABLE history ADD COLUMN revision_of INTEGER")
+                .map_err(history_error)?;
+        }
+
+        Ok(Self { connection })
+    }
+
+    /// 写入一条记录，返回新记录的 ID
+    pub fn add(&self, entry: &HistoryEntry) -> Result<i64> {
+        self.connection
+            .execute(
+                "INSERT INTO history (created_at, engine, language, duration, output, text, segments, revision_of)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    entry.created_at.to_rfc3339(),
+                    entry.engine,
+                    entry.language,
+                    entry.duration,
+                    entry.output,
+                    entry.text,
+                    serde_json::to_string(&entry.segments).unwrap_or_else(|_| "[]".to_string()),
+                    entry.revision_of,
+                ],
+            )
+            .map_err(history_error)?;
+
+        Ok(self.connection.last_insert_rowid())
+    }
+
+    /// 写入一条语音编辑的结果，关联到最近一条记录所修改的原文
+    pub fn add_revision(&self, entry: &HistoryEntry) -> Result<i64> {
+        let original = self.recent(1)?.first().map(|last| last.revision_of.unwrap_or(last.id));
+        self.add(&HistoryEntry {
+            revision_of: original,
+            ..entry.clone()
+        })
+    }
+
+    /// 撤销最近一次语音编辑：最近一条记录是编辑结果时删除它，返回是否删除
+    pub fn remove_last_revision(&self) -> Result<bool> {
+        let deleted = self
+            .connection
+            .execute(
+                "DELETE FROM history WHERE id = (SELECT MAX(id) FROM history) AND revision_of IS NOT NULL",
+                [],
+            )
+            .map_err(history_error)?;
+        Ok(deleted > 0)
+    }
+
+    /// 最近的记录（新的在前）
+    pub fn recent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        self.query(
+            &format!("SELECT {} FROM history ORDER BY id DESC LIMIT ?1", COLUMNS),
+            params![limit as i64],
+        )
+    }
+
+    /// 按关键词搜索（不区分大小写，新的在前）
+    pub fn search(&self, keyword: &str, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let pattern = format!(
+            "%{}%",
+            keyword
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        self.query(
+            &format!(
+                "SELECT {} FROM history WHERE text LIKE ?1 ESCAPE '\\' ORDER BY id DESC LIMIT ?2",
+                COLUMNS
+            ),
+            params![pattern, limit as i64],
+        )
+    }
+
+    /// 按 ID 获取记录
+    pub fn get(&self, id: i64) -> Result<Option<HistoryEntry>> {
+        self.connection
+            .query_row(
+                &format!("SELECT {} FROM history WHERE id = ?1", COLUMNS),
+                params![id],
+                HistoryEntry::from_row,
+            )
+            .optional()
+            .map_err(history_error)
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<HistoryEntry>> {
+        let mut statement = self.connection.prepare(sql).map_err(history_error)?;
+        let rows = statement
+            .query_map(params, HistoryEntry::from_row)
+            .map_err(history_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(history_error)
+    }
+}
+
+fn history_error(e: rusqlite::Error) -> VoiceInputError {
+    VoiceInputError::History(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: 0,
+            created_at: Local::now(),
+            engine: "xfyun".to_string(),
+            language: "zh_cn".to_string(),
+            duration: 3.5,
+            output: "clipboard".to_string(),
+            text: text.to_string(),
+            segments: vec![Segment {
+                start_ms: 100,
+                end_ms: 2000,
+                text: text.to_string(),
+                words: Vec::new(),
+            }],
+            revision_of: None,
+        }
+    }
+
+    #[test]
+    fn test_add_and_get() {
+        let store = HistoryStore::open_in_memory().unwrap();
+        let id = store.add(&entry("今天天气很好")).unwrap();
+
+        let saved = store.get(id).unwrap().unwrap();
+        assert_eq!(saved.id, id);
+        assert_eq!(saved.text, "今天天气很好");
+        assert_eq!(saved.duration, 3.5);
+        assert_eq!(saved.segments, entry("今天天气很好").segments);
+        assert!(store.get(id + 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_recent_and_search() {
+        let store = HistoryStore::open_in_memory().unwrap();
+        store.add(&entry("第一条")).unwrap();
+        store.add(&entry("第二条 100% 完成")).unwrap();
+        store.add(&entry("第三条")).unwrap();
+
+        let recent = store.recent(2).unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].text, "第三条");
+
+        assert_eq!(store.search("条", 10).unwrap().len(), 3);
+        // % 按字面匹配
+        let found = store.search("100%", 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "第二条 100% 完成");
+        assert!(store.search("%%", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_revisions() {
+        let store = HistoryStore::open_in_memory().unwrap();
+        let original = store.add(&entry("请张三准备材料")).unwrap();
+        store.add_revision(&entry("请李四准备材料")).unwrap();
+        let second = store.add_revision(&entry("请李四准备")).unwrap();
+
+        // 连续编辑都关联到同一条原文
+        let saved = store.get(second).unwrap().unwrap();
+        assert_eq!(saved.revision_of, Some(original));
+        assert_eq!(store.recent(1).unwrap()[0].text, "请李四准备");
+
+        assert!(store.remove_last_revision().unwrap());
+        assert_eq!(store.recent(1).unwrap()[0].text, "请李四准备材料");
+        assert!(store.remove_last_revision().unwrap());
+        // 原文本身不会被撤销删除
+        assert!(!store.remove_last_revision().unwrap());
+        assert_eq!(store.recent(1).unwrap()[0].id, original);
+    }
+
+    #[test]
+    fn test_file_permissions() {
+        let dir = std::env::temp_dir().join(format!("voice-input-history-{}", std::process::id()));
+        let path = dir.join("data").join("history.db");
+        HistoryStore::open(&path).unwrap().add(&entry("私密内容")).unwrap();
+
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(mode(path.parent().unwrap()), 0o700);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_preview() {
+        let entry = entry("第一行\n第二行");
+        assert_eq!(entry.preview(20), "第一行 第二行");
+        assert_eq!(entry.preview(3), "第一行…");
+    }
+}
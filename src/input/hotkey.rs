@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use evdev::KeyCode;
+use tokio::sync::mpsc;
+
+use super::keyboard::{open_keyboards, parse_key, spawn_key_listener};
+use super::portal::run_portal_shortcuts;
+use crate::config::HotkeyConfig;
+use crate::daemon::DaemonCommand;
+use crate::utils::{Result, VoiceInputError};
+
+/// 修饰键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    const ALL: [Modifier; 4] = [Self::Ctrl, Self::Shift, Self::Alt, Self::Super];
+
+    /// 对应的左右两个物理按键
+    fn keys(&self) -> [KeyCode; 2] {
+        match self {
+            Self::Ctrl => [KeyCode::KEY_LEFTCTRL, KeyCode::KEY_RIGHTCTRL],
+            Self::Shift => [KeyCode::KEY_LEFTSHIFT, KeyCode::KEY_RIGHTSHIFT],
+            Self::Alt => [KeyCode::KEY_LEFTALT, KeyCode::KEY_RIGHTALT],
+            Self::Super => [KeyCode::KEY_LEFTMETA, KeyCode::KEY_RIGHTMETA],
+        }
+    }
+
+    /// XDG 快捷键规范中的名称
+    fn portal_name(&self) -> &'static str {
+        match self {
+            Self::Ctrl => "CTRL",
+            Self::Shift => "SHIFT",
+            Self::Alt => "ALT",
+            Self::Super => "LOGO",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" | "primary" => Some(Self::Ctrl),
+            "shift" => Some(Self::Shift),
+            "alt" | "mod1" => Some(Self::Alt),
+            "super" | "meta" | "logo" | "win" | "mod4" => Some(Self::Super),
+            _ => None,
+        }
+    }
+}
+
+/// 组合键，例如 `Super+Shift+B`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    /// 需要同时按住的修饰键（已排序、去重）
+    pub modifiers: Vec<Modifier>,
+    /// 触发按键
+    pub key: KeyCode,
+}
+
+impl FromStr for Chord {
+    type Err = VoiceInputError;
+
+    /// 支持 `Super+Shift+B` 和 GNOME 的 `<Super><Shift>b` 两种写法
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.replace('<', "").replace('>', "+");
+        let parts: Vec<&str> = normalized
+            .split('+')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        let (key_name, modifier_names) = parts
+            .split_last()
+            .ok_or_else(|| VoiceInputError::Config(format!("快捷键为空: {:?}", s)))?;
+
+        let mut modifiers = modifier_names
+            .iter()
+            .map(|name| {
+                Modifier::parse(name)
+                    .ok_or_else(|| VoiceInputError::Config(format!("未知修饰键: {}", name)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        modifiers.sort();
+        modifiers.dedup();
+
+        Ok(Self {
+            modifiers,
+            key: parse_key(key_name)?,
+        })
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{:?}+", modifier)?;
+        }
+        let name = format!("{:?}", self.key);
+        write!(f, "{}", name.trim_start_matches("KEY_"))
+    }
+}
+
+impl Chord {
+    /// 转为 XDG GlobalShortcuts 门户使用的触发键描述（如 `LOGO+SHIFT+b`）
+    pub fn to_portal_trigger(&self) -> String {
+        let name = format!("{:?}", self.key);
+        let key = name.trim_start_matches("KEY_");
+        let key = match key {
+            "ESC" => "Escape".to_string(),
+            "SPACE" => "space".to_string(),
+            "ENTER" => "Return".to_string(),
+            k if k.len() == 1 => k.to_ascii_lowercase(),
+            k => k.to_string(),
+        };
+
+        let mut parts: Vec<&str> = self.modifiers.iter().map(Modifier::portal_name).collect();
+        parts.push(&key);
+        parts.join("+")
+    }
+
+    /// 当前按下的按键是否正好组成这个组合键（不允许多余的修饰键）
+    fn matches(&self, key: KeyCode, pressed: &HashSet<KeyCode>) -> bool {
+        key == self.key
+            && Modifier::ALL.iter().all(|modifier| {
+                let held = modifier.keys().iter().any(|k| pressed.contains(k));
+                held == self.modifiers.contains(modifier)
+            })
+    }
+}
+
+/// 监听全局快捷键，触发时把绑定的命令发送到 `commands`
+///
+/// - `evdev`：读取 /dev/input，X11 / Wayland 通用，需要 input 组权限
+/// - `portal`：通过 XDG GlobalShortcuts 门户注册，由桌面环境负责按键捕获
+pub async fn run_hotkeys(
+    config: &HotkeyConfig,
+    commands: mpsc::UnboundedSender<DaemonCommand>,
+) -> Result<()> {
+    let bindings = config
+        .bindings
+        .iter()
+        .map(|binding| Ok((binding.chord.parse::<Chord>()?, binding.action)))
+        .collect::<Result<Vec<(Chord, DaemonCommand)>>>()?;
+
+    if bindings.is_empty() {
+        return Err(VoiceInputError::Config("未配置任何快捷键".to_string()));
+    }
+
+    for (chord, action) in &bindings {
+        tracing::info!("注册快捷键: {} → {}", chord, action.as_str());
+    }
+
+    match config.backend.as_str() {
+        "evdev" => run_evdev(config.device.as_deref(), &bindings, commands).await,
+        "portal" => run_portal_shortcuts(&bindings, commands).await,
+        other => Err(VoiceInputError::Config(format!(
+            "未知快捷键后端: {}（可选: evdev | portal）",
+            other
+        ))),
+    }
+}
+
+/// evdev 后端：跟踪修饰键状态，按下触发键时比较组合
+async fn run_evdev(
+    device: Option<&str>,
+    bindings: &[(Chord, DaemonCommand)],
+    commands: mpsc::UnboundedSender<DaemonCommand>,
+) -> Result<()> {
+    let trigger_keys: Vec<KeyCode> = bindings.iter().map(|(chord, _)| chord.key).collect();
+    let mut watched = trigger_keys.clone();
+    watched.extend(Modifier::ALL.iter().flat_map(|m| m.keys()));
+
+    let devices = open_keyboards(device, &trigger_keys)?;
+    let mut presses = spawn_key_listener(devices, &watched)?;
+    let mut pressed = HashSet::new();
+
+    while let Some(press) = presses.recv().await {
+        if press.is_release() {
+            pressed.remove(&press.key);
+            continue;
+        }
+        if !press.is_press() {
+            continue;
+        }
+
+        pressed.insert(press.key);
+        for (chord, action) in bindings {
+            if chord.matches(press.key, &pressed) {
+                tracing::info!("快捷键触发: {} → {}", chord, action.as_str());
+                if commands.send(*action).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Err(VoiceInputError::InputDevice("所有输入设备都已断开".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chord() {
+        let chord: Chord = "Super+Shift+B".parse().unwrap();
+        assert_eq!(chord.modifiers, vec![Modifier::Shift, Modifier::Super]);
+        assert_eq!(chord.key, KeyCode::KEY_B);
+
+        let gnome: Chord = "<Super><Shift>b".parse().unwrap();
+        assert_eq!(gnome, chord);
+
+        assert!("Hyper+B".parse::<Chord>().is_err());
+        assert!("".parse::<Chord>().is_err());
+    }
+
+    #[test]
+    fn test_portal_trigger() {
+        let chord: Chord = "Super+Shift+B".parse().unwrap();
+        assert_eq!(chord.to_portal_trigger(), "SHIFT+LOGO+b");
+
+        let chord: Chord = "Ctrl+Alt+space".parse().unwrap();
+        assert_eq!(chord.to_portal_trigger(), "CTRL+ALT+space");
+    }
+
+    #[test]
+    fn test_chord_matches() {
+        let chord: Chord = "Super+Shift+B".parse().unwrap();
+        let mut pressed: HashSet<KeyCode> =
+            [KeyCode::KEY_LEFTMETA, KeyCode::KEY_RIGHTSHIFT, KeyCode::KEY_B].into();
+        assert!(chord.matches(KeyCode::KEY_B, &pressed));
+
+        // 多按了 Ctrl 不应触发
+        pressed.insert(KeyCode::KEY_LEFTCTRL);
+        assert!(!chord.matches(KeyCode::KEY_B, &pressed));
+
+        // 缺少 Shift 不应触发
+        let pressed: HashSet<KeyCode> = [KeyCode::KEY_LEFTMETA, KeyCode::KEY_B].into();
+        assert!(!chord.matches(KeyCode::KEY_B, &pressed));
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use evdev::{Device, EventSummary, KeyCode};
+use tokio::sync::mpsc;
+
+use crate::utils::{Result, VoiceInputError};
+
+/// 一次按键事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    /// 按键
+    pub key: KeyCode,
+    /// 1: 按下, 0: 松开, 2: 自动重复
+    pub value: i32,
+}
+
+impl KeyPress {
+    /// 是否为按下（不含自动重复）
+    pub fn is_press(&self) -> bool {
+        self.value == 1
+    }
+
+    /// 是否为松开
+    pub fn is_release(&self) -> bool {
+        self.value == 0
+    }
+}
+
+/// 解析按键名
+///
+/// 支持 evdev 键名（`KEY_RIGHTCTRL`），也可以省略前缀、不区分大小写（`rightctrl`）。
+pub fn parse_key(name: &str) -> Result<KeyCode> {
+    let upper = name.trim().to_ascii_uppercase();
+    let full = if upper.starts_with("KEY_") || upper.starts_with("BTN_") {
+        upper
+    } else {
+        format!("KEY_{}", upper)
+    };
+
+    KeyCode::from_str(&full)
+        .map_err(|_| VoiceInputError::Config(format!("未知按键: {}", name)))
+}
+
+/// 打开键盘设备
+///
+/// 指定 `device` 时只打开该设备；否则打开所有支持 `keys` 中全部按键的设备。
+pub fn open_keyboards(device: Option<&str>, keys: &[KeyCode]) -> Result<Vec<(PathBuf, Device)>> {
+    if let Some(path) = device.filter(|p| !p.is_empty()) {
+        let path = PathBuf::from(shellexpand::tilde(path).into_owned());
+        let device = Device::open(&path)
+            .map_err(|e| VoiceInputError::InputDevice(format!("打开 {} 失败: {}", path.display(), e)))?;
+        return Ok(vec![(path, device)]);
+    }
+
+    let devices: Vec<(PathBuf, Device)> = evdev::enumerate()
+        .filter(|(_, device)| {
+            device
+                .supported_keys()
+                .is_some_and(|supported| keys.iter().all(|key| supported.contains(*key)))
+        })
+        .collect();
+
+    if devices.is_empty() {
+        return Err(VoiceInputError::InputDevice(format!(
+            "未找到带有按键 {:?} 的输入设备（或没有 /dev/input 读取权限）",
+            keys
+        )));
+    }
+
+    for (path, device) in &devices {
+        tracing::info!(
+            "监听输入设备: {} ({})",
+            path.display(),
+            device.name().unwrap_or("Unknown")
+        );
+    }
+
+    Ok(devices)
+}
+
+/// 在后台监听设备上的按键事件
+///
+/// 只转发 `keys` 中的按键；所有设备的事件汇总到同一个通道。
+pub fn spawn_key_listener(
+    devices: Vec<(PathBuf, Device)>,
+    keys: &[KeyCode],
+) -> Result<mpsc::UnboundedReceiver<KeyPress>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    for (path, device) in devices {
+        let mut stream = device
+            .into_event_stream()
+            .map_err(|e| VoiceInputError::InputDevice(format!("监听 {} 失败: {}", path.display(), e)))?;
+        let tx = tx.clone();
+        let keys = keys.to_vec();
+
+        tokio::spawn(async move {
+            loop {
+                match stream.next_event().await {
+                    Ok(event) => {
+                        if let EventSummary::Key(_, key, value) = event.destructure() {
+                            if keys.contains(&key) && tx.send(KeyPress { key, value }).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("读取输入设备 {} 失败: {}", path.display(), e);
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    Ok(rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("KEY_RIGHTCTRL").unwrap(), KeyCode::KEY_RIGHTCTRL);
+        assert_eq!(parse_key("rightctrl").unwrap(), KeyCode::KEY_RIGHTCTRL);
+        assert_eq!(parse_key(" f13 ").unwrap(), KeyCode::KEY_F13);
+        assert_eq!(parse_key("BTN_SIDE").unwrap(), KeyCode::BTN_SIDE);
+        assert!(parse_key("not_a_key").is_err());
+    }
+}
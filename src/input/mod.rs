@@ -0,0 +1,10 @@
+pub mod hotkey;
+pub mod keyboard;
+pub mod picker;
+pub mod portal;
+pub mod ptt;
+
+pub use hotkey::{run_hotkeys, Chord};
+pub use keyboard::{open_keyboards, parse_key, spawn_key_listener, KeyPress};
+pub use picker::pick_alternatives;
+pub use ptt::run_push_to_talk;
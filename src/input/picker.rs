@@ -0,0 +1,74 @@
+use std::io::{BufRead, IsTerminal, Write};
+
+use crate::transcript::Transcript;
+use crate::utils::Result;
+
+/// 在终端中让用户把词换成候选词
+///
+/// 列出所有带候选的词，用户输入 `词序号 候选序号` 进行替换，空行结束。
+/// 标准输入不是终端或没有任何候选时直接返回。
+pub fn pick_alternatives(transcript: &mut Transcript) -> Result<()> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() || transcript.words().all(|w| w.alternatives.is_empty()) {
+        return Ok(());
+    }
+
+    let mut lines = stdin.lock().lines();
+    loop {
+        println!("\n📝 {}", transcript.text());
+        println!("🔤 可替换的词：");
+        for (index, word) in transcript.words().enumerate() {
+            if word.alternatives.is_empty() {
+                continue;
+            }
+            let alternatives: Vec<String> = word
+                .alternatives
+                .iter()
+                .enumerate()
+                .map(|(i, alternative)| format!("{}) {}", i + 1, alternative))
+                .collect();
+            println!("  [{}] {} → {}", index + 1, word.text, alternatives.join("  "));
+        }
+        print!("输入 \"词序号 候选序号\" 替换（如 2 1），直接回车完成: ");
+        std::io::stdout().flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(());
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+
+        match parse_choice(line) {
+            Some((word, alternative)) if transcript.choose_alternative(word, alternative) => {}
+            _ => println!("⚠️  无效的输入: {}", line),
+        }
+    }
+}
+
+/// 解析 `词序号 候选序号`（从 1 开始），返回从 0 开始的序号
+fn parse_choice(line: &str) -> Option<(usize, usize)> {
+    let mut parts = line.split_whitespace().map(|p| p.parse::<usize>().ok());
+    let word = parts.next()??.checked_sub(1)?;
+    let alternative = parts.next().unwrap_or(Some(1))?.checked_sub(1)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((word, alternative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_choice() {
+        assert_eq!(parse_choice("2 1"), Some((1, 0)));
+        // 省略候选序号时使用第一个候选
+        assert_eq!(parse_choice("3"), Some((2, 0)));
+        assert_eq!(parse_choice("0 1"), None);
+        assert_eq!(parse_choice("a b"), None);
+        assert_eq!(parse_choice("1 2 3"), None);
+    }
+}
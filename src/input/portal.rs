@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+use zbus::{Connection, MatchRule, MessageStream, Proxy};
+
+use super::hotkey::Chord;
+use crate::daemon::DaemonCommand;
+use crate::utils::{Result, VoiceInputError};
+
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const GLOBAL_SHORTCUTS: &str = "org.freedesktop.portal.GlobalShortcuts";
+
+/// XDG GlobalShortcuts 门户后端
+///
+/// 创建会话并绑定快捷键后，监听 `Activated` 信号。首次绑定时桌面环境
+/// 通常会弹出确认对话框，用户也可以在系统设置中修改实际的按键。
+pub async fn run_portal_shortcuts(
+    bindings: &[(Chord, DaemonCommand)],
+    commands: mpsc::UnboundedSender<DaemonCommand>,
+) -> Result<()> {
+    let connection = Connection::session().await.map_err(portal_error)?;
+    let proxy = Proxy::new(&connection, PORTAL_DESTINATION, PORTAL_PATH, GLOBAL_SHORTCUTS)
+        .await
+        .map_err(portal_error)?;
+
+    // 先订阅 Activated，避免绑定完成后错过信号
+    let mut activated = proxy.receive_signal("Activated").await.map_err(portal_error)?;
+
+    // 创建会话
+    let mut options: HashMap<&str, Value> = HashMap::new();
+    options.insert("handle_token", Value::from("voice_input_session"));
+    options.insert("session_handle_token", Value::from("voice_input"));
+    let results = portal_request(&connection, &proxy, "CreateSession", &(options,), "voice_input_session").await?;
+    let session_handle: String = results
+        .get("session_handle")
+        .and_then(|v| String::try_from(v.clone()).ok())
+        .ok_or_else(|| VoiceInputError::InputDevice("门户未返回会话句柄".to_string()))?;
+    let session = OwnedObjectPath::try_from(session_handle).map_err(|e| portal_error(e.into()))?;
+
+    // 绑定快捷键（id 使用绑定序号）
+    let shortcuts: Vec<(String, HashMap<&str, Value>)> = bindings
+        .iter()
+        .enumerate()
+        .map(|(index, (chord, action))| {
+            let mut properties: HashMap<&str, Value> = HashMap::new();
+            properties.insert("description", Value::from(format!("语音输入: {}", action.as_str())));
+            properties.insert("preferred_trigger", Value::from(chord.to_portal_trigger()));
+            (shortcut_id(index), properties)
+        })
+        .collect();
+    let mut options: HashMap<&str, Value> = HashMap::new();
+    options.insert("handle_token", Value::from("voice_input_bind"));
+    portal_request(
+        &connection,
+        &proxy,
+        "BindShortcuts",
+        &(&session, shortcuts, "", options),
+        "voice_input_bind",
+    )
+    .await?;
+
+    tracing::info!("已通过 XDG 门户注册全局快捷键");
+
+    while let Some(signal) = activated.next().await {
+        let (_, id, _, _): (OwnedObjectPath, String, u64, HashMap<String, OwnedValue>) =
+            match signal.body().deserialize() {
+                Ok(body) => body,
+                Err(e) => {
+                    tracing::warn!("无法解析快捷键信号: {}", e);
+                    continue;
+                }
+            };
+
+        let action = (0..bindings.len())
+            .find(|index| shortcut_id(*index) == id)
+            .map(|index| bindings[index].1);
+        if let Some(action) = action {
+            tracing::info!("快捷键触发: {} → {}", id, action.as_str());
+            if commands.send(action).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(VoiceInputError::InputDevice("XDG 门户连接已断开".to_string()))
+}
+
+fn shortcut_id(index: usize) -> String {
+    format!("voice-input-{}", index)
+}
+
+/// 调用门户方法并等待对应 Request 对象的 Response 信号
+async fn portal_request<B>(
+    connection: &Connection,
+    proxy: &Proxy<'_>,
+    method: &str,
+    body: &B,
+    token: &str,
+) -> Result<HashMap<String, OwnedValue>>
+where
+    B: serde::Serialize + zbus::zvariant::DynamicType,
+{
+    // Request 对象路径由调用方唯一名和 handle_token 决定，需要在调用前订阅
+    let sender = connection
+        .unique_name()
+        .map(|name| name.trim_start_matches(':').replace('.', "_"))
+        .ok_or_else(|| VoiceInputError::InputDevice("D-Bus 连接没有唯一名".to_string()))?;
+    let request_path = format!("{}/request/{}/{}", PORTAL_PATH, sender, token);
+
+    let rule = MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .interface("org.freedesktop.portal.Request")
+        .and_then(|b| b.member("Response"))
+        .and_then(|b| b.path(request_path.as_str()))
+        .map_err(portal_error)?
+        .build();
+    let mut responses = MessageStream::for_match_rule(rule, connection, None)
+        .await
+        .map_err(portal_error)?;
+
+    proxy.call_method(method, body).await.map_err(portal_error)?;
+
+    let message = responses
+        .next()
+        .await
+        .ok_or_else(|| VoiceInputError::InputDevice(format!("门户 {} 无响应", method)))?
+        .map_err(portal_error)?;
+    let (code, results): (u32, HashMap<String, OwnedValue>) =
+        message.body().deserialize().map_err(portal_error)?;
+
+    if code != 0 {
+        return Err(VoiceInputError::InputDevice(format!(
+            "门户 {} 被拒绝或取消（代码 {}）",
+            method, code
+        )));
+    }
+
+    Ok(results)
+}
+
+fn portal_error(e: zbus::Error) -> VoiceInputError {
+    VoiceInputError::InputDevice(format!("XDG 门户: {}", e))
+}
@@ -0,0 +1,114 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::task::{JoinHandle, LocalSet};
+
+use super::keyboard::{open_keyboards, parse_key, spawn_key_listener};
+use crate::mode::{Mode, Revisions};
+use crate::online::{RecognitionEvent, MAX_SILENCE_DURATION};
+use crate::session::{connect_notifier, run_session, SessionOptions};
+use crate::utils::{Result, VoiceInputError};
+use crate::Config;
+
+/// 按住说话：按住配置的按键时录音，松开后立即发送结束帧并输出结果
+///
+/// 按键事件直接从 /dev/input 读取，不依赖桌面环境的全局快捷键，
+/// 因此在 X11 和 Wayland 下都能使用。收到 SIGHUP 时重新读取文本后处理规则和语音命令。
+pub async fn run_push_to_talk(mut config: Config, streaming: bool, mode: Mode) -> Result<()> {
+    let key = parse_key(&config.push_to_talk.key)?;
+    let devices = open_keyboards(config.push_to_talk.device.as_deref(), &[key])?;
+    let mut presses = spawn_key_listener(devices, &[key])?;
+    let mut sighup = signal(SignalKind::hangup())?;
+
+    println!("🎙️  按住说话已就绪：按住 {:?} 开始录音，松开结束", key);
+
+    let local = LocalSet::new();
+    local
+        .run_until(async move {
+            // （运行标志，是否已连接并开始录音，会话任务）
+            let mut active: Option<(Arc<AtomicBool>, Arc<AtomicBool>, JoinHandle<()>)> = None;
+            let revisions = Arc::new(Mutex::new(Revisions::default()));
+            let notifier = connect_notifier(&config).await;
+
+            loop {
+                let press = tokio::select! {
+                    press = presses.recv() => match press {
+                        Some(press) => press,
+                        None => {
+                            return Err(VoiceInputError::InputDevice(
+                                "所有输入设备都已断开".to_string(),
+                            ))
+                        }
+                    },
+                    _ = tokio::signal::ctrl_c() => break,
+                    _ = sighup.recv() => {
+                        match config.reload_processing() {
+                            Ok(()) => println!("🔄 已重新加载文本后处理规则和语音命令"),
+                            Err(e) => eprintln!("❌ {}", e.user_message()),
+                        }
+                        continue;
+                    }
+                };
+
+                if press.is_press() {
+                    if active.as_ref().is_some_and(|(_, _, handle)| !handle.is_finished()) {
+                        tracing::info!("上一次识别尚未结束，忽略按键");
+                        continue;
+                    }
+
+                    let running = Arc::new(AtomicBool::new(true));
+                    let recording = Arc::new(AtomicBool::new(false));
+                    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+                    let recording_flag = recording.clone();
+                    tokio::task::spawn_local(async move {
+                        while let Some(event) = events_rx.recv().await {
+                            if matches!(event, RecognitionEvent::Recording) {
+                                recording_flag.store(true, Ordering::SeqCst);
+                            }
+                        }
+                    });
+                    let options = SessionOptions {
+                        streaming,
+                        mode,
+                        running: Some(running.clone()),
+                        events: Some(events_tx),
+                        revisions: Some(revisions.clone()),
+                        notifier: notifier.clone(),
+                        // 由松开按键结束录音，尽量不让静音检测提前结束
+                        silence_duration: Some(MAX_SILENCE_DURATION),
+                        ..Default::default()
+                    };
+                    let config = config.clone();
+                    let handle = tokio::task::spawn_local(async move {
+                        match run_session(&config, options).await {
+                            Ok(transcript) => println!("📝 识别结果: {}", transcript.text()),
+                            Err(e) => eprintln!("❌ {}", e.user_message()),
+                        }
+                    });
+                    active = Some((running, recording, handle));
+                } else if press.is_release() {
+                    match active.take() {
+                        // 还没连上识别服务就松开：没有发送过音频，直接取消会话
+                        Some((running, recording, handle)) if !recording.load(Ordering::SeqCst) => {
+                            running.store(false, Ordering::SeqCst);
+                            handle.abort();
+                            println!("⏹️  按键时间太短，已取消");
+                        }
+                        Some((running, recording, handle)) => {
+                            running.store(false, Ordering::SeqCst);
+                            active = Some((running, recording, handle));
+                        }
+                        None => {}
+                    }
+                }
+            }
+
+            if let Some((running, _, handle)) = active {
+                running.store(false, Ordering::SeqCst);
+                handle.abort();
+            }
+            Ok(())
+        })
+        .await
+}
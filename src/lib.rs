@@ -1,6 +1,14 @@
 pub mod audio;
 pub mod config;
+pub mod daemon;
+pub mod export;
+pub mod history;
+pub mod input;
+pub mod mode;
 pub mod output;
+pub mod processing;
+pub mod session;
+pub mod transcript;
 pub mod utils;
 pub mod online;
 
@@ -1,7 +1,99 @@
-use linux_voice_input_rs::{online::XfyunRealtimeRecognizer, output::ClipboardOutput, Config};
+use std::io::IsTerminal;
+
+use clap::{Parser, Subcommand};
+use linux_voice_input_rs::{
+    daemon::{self, DaemonCommand, DaemonOptions},
+    export::{self, ExportFormat},
+    history::{HistoryEntry, HistoryStore},
+    input,
+    mode::Mode,
+    output::{ClipboardOutput, ConfidenceMarker, DesktopNotifier},
+    session::{run_session, SessionOptions},
+    transcript::Segment,
+    Config, VoiceInputError,
+};
+
+/// Linux 中文语音输入工具
+#[derive(Parser, Debug)]
+#[command(name = "voice-input", version, about)]
+struct Cli {
+    /// 流式输出：边识别边向当前焦点窗口输入文字
+    #[arg(long, global = true)]
+    stream: bool,
+
+    /// 输出前在终端中把识别结果里的词换成候选词
+    #[arg(long)]
+    pick: bool,
+
+    /// 听写模式：dictation（普通听写）| markdown（口述结构命令，累积成一篇文档）| code（口述命名风格和符号）| command（语音命令）
+    #[arg(long, global = true, value_enum, default_value = "dictation")]
+    mode: Mode,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// 以守护进程方式常驻，通过 Unix 套接字接收控制命令
+    Daemon,
+    /// 以守护进程方式常驻，并显示系统托盘图标
+    Tray,
+    /// 按住说话：按住配置的按键时录音，松开结束（读取 /dev/input）
+    Ptt,
+    /// 监听全局快捷键并把命令发送给守护进程（evdev 或 XDG 门户）
+    Hotkey,
+    /// 向守护进程发送控制命令（toggle | start | stop | cancel | status | reload）
+    Ctl {
+        #[arg(value_enum)]
+        command: DaemonCommand,
+    },
+    /// 查看、搜索和重新复制听写历史
+    History {
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum HistoryCommand {
+    /// 列出最近的记录
+    List {
+        /// 显示条数
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// 按关键词搜索
+    Search {
+        keyword: String,
+        /// 显示条数
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// 显示一条记录的完整内容
+    Show { id: i64 },
+    /// 把一条记录重新复制到剪贴板
+    Copy { id: i64 },
+    /// 导出为 JSON / Markdown / SRT / WebVTT（含分段时间）
+    Export {
+        /// 要导出的记录 ID（为空时导出最近的记录）
+        ids: Vec<i64>,
+        /// 导出格式
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: ExportFormat,
+        /// 未指定 ID 时导出最近几条
+        #[arg(short = 'n', long, default_value_t = 1)]
+        last: usize,
+        /// 输出文件（默认输出到终端）
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+}
 
 #[tokio::main]
 async fn main() -> linux_voice_input_rs::Result<()> {
+    let cli = Cli::parse();
+
     // 初始化日志
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -10,54 +102,229 @@ async fn main() -> linux_voice_input_rs::Result<()> {
         )
         .init();
 
+    match cli.command {
+        None => run_once(cli.stream, cli.pick, cli.mode).await,
+        Some(command @ (Commands::Daemon | Commands::Tray)) => {
+            let config = Config::load()?;
+            let options = DaemonOptions {
+                streaming: cli.stream || config.output.streaming,
+                tray: matches!(command, Commands::Tray),
+                mode: cli.mode,
+            };
+            daemon::run_daemon(config, options).await
+        }
+        Some(Commands::Ptt) => {
+            let config = Config::load()?;
+            let streaming = cli.stream || config.output.streaming;
+            input::run_push_to_talk(config, streaming, cli.mode).await
+        }
+        Some(Commands::Hotkey) => {
+            let config = Config::load()?;
+            run_hotkey_client(config).await
+        }
+        Some(Commands::Ctl { command }) => {
+            let response = daemon::send_command(command)?;
+            println!("{}", response.message);
+            if !response.ok {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Commands::History { command }) => {
+            let config = Config::load()?;
+            run_history(&config, command)
+        }
+    }
+}
+
+/// 单次听写：录音识别一次后退出
+async fn run_once(stream: bool, pick: bool, mode: Mode) -> linux_voice_input_rs::Result<()> {
     println!("🎙️  Linux Voice Input - 讯飞云在线版");
     println!("=====================================\n");
 
-    // 加载配置
-    let config = Config::load()?;
+    // 加载配置（从快捷键启动时看不到终端，失败时也要发桌面通知）
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            notify_error(&e).await;
+            return Err(e);
+        }
+    };
+    let streaming = stream || config.output.streaming;
 
     println!("📝 配置信息:");
     println!("  - 采样率: {} Hz", config.audio.sample_rate);
     println!("  - 声道数: {}", config.audio.channels);
     println!("  - 静音阈值: {}", config.whisper.silence_threshold);
     println!("  - 静音持续: {:.1} 秒", config.whisper.silence_duration);
-    println!("  - 讯飞云 App ID: {}\n", config.xfyun.app_id);
-
-    // 检查讯飞云配置
-    if config.xfyun.app_id.is_empty()
-        || config.xfyun.api_secret.is_empty()
-        || config.xfyun.api_key.is_empty()
-    {
-        eprintln!("❌ 错误: 请在 config.toml 中配置讯飞云 API 密钥");
-        eprintln!("   需要设置: app_id, api_secret, api_key");
-        std::process::exit(1);
-    }
-
-    // 创建讯飞云实时识别器
-    let recognizer = XfyunRealtimeRecognizer::new(
-        config.xfyun.app_id.clone(),
-        config.xfyun.api_secret.clone(),
-        config.xfyun.api_key.clone(),
-    );
-
-    // 实时识别（边录边发送）
-    let text = recognizer
-        .recognize_realtime(
-            config.audio.sample_rate,
-            config.whisper.silence_duration,
-        )
-        .await?;
+    println!("  - 讯飞云 App ID: {}", config.xfyun.app_id);
+    if streaming {
+        println!("  - 流式输出: {}", config.output.typing_tool);
+    }
+    if mode == Mode::Markdown {
+        println!("  - Markdown 文档: {}", config.markdown.document_path().display());
+    }
+    if mode == Mode::Code {
+        println!("  - 代码模式识别语言: {}", config.code.language);
+    }
+    if mode == Mode::Command {
+        println!("  - 语音命令: {} 条", config.commands.len());
+    }
+    println!();
+
+    // 实时识别并输出（未配置 API 密钥等错误由会话发送桌面通知）
+    let options = SessionOptions {
+        streaming,
+        pick,
+        mode,
+        ..Default::default()
+    };
+    let transcript = run_session(&config, options).await?;
 
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("📝 识别结果:");
-    println!("{}", text);
+    print_result(&config, &transcript.text(), &transcript.segments);
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    // 复制到剪贴板
-    let mut clipboard = ClipboardOutput::new()?;
-    clipboard.copy(&text)?;
+    if mode == Mode::Command {
+        println!("📜 执行记录: {}", config.command.log_path().display());
+        return Ok(());
+    }
+    if streaming {
+        println!("⌨️  已输入到当前窗口");
+    }
+    match &config.output.file_path {
+        Some(path) if config.output.to_file() => println!("✅ 已写入 {}", path),
+        _ if mode == Mode::Markdown => println!("✅ 已将整篇文档复制到剪贴板"),
+        _ => println!("✅ 已复制到剪贴板，可以直接粘贴使用！"),
+    }
+
+    Ok(())
+}
+
+/// 会话开始前的错误：尽量通过桌面通知告诉用户
+async fn notify_error(error: &VoiceInputError) {
+    match DesktopNotifier::connect().await {
+        Ok(notifier) => {
+            if let Err(e) = notifier.error(error).await {
+                tracing::warn!("{}", e);
+            }
+        }
+        Err(e) => tracing::warn!("{}", e),
+    }
+}
+
+/// 在终端中显示识别结果，开启置信度标记时高亮低置信度的词
+fn print_result(config: &Config, text: &str, segments: &[Segment]) {
+    let marker = ConfidenceMarker::from_config(&config.confidence)
+        .filter(|_| std::io::stdout().is_terminal());
+
+    match marker {
+        Some(marker) => {
+            println!("{}", marker.colorize(text, segments));
+            let low = marker.count(segments);
+            if low > 0 {
+                println!("⚠️  {} 个词置信度较低（已高亮），请注意校对", low);
+            }
+        }
+        None => println!("{}", text),
+    }
+}
 
-    println!("✅ 已复制到剪贴板，可以直接粘贴使用！");
+/// 独立的快捷键监听进程：触发时通过套接字通知守护进程
+async fn run_hotkey_client(config: Config) -> linux_voice_input_rs::Result<()> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<DaemonCommand>();
 
+    tokio::spawn(async move {
+        while let Some(command) = rx.recv().await {
+            let sent = tokio::task::spawn_blocking(move || daemon::send_command(command)).await;
+            match sent {
+                Ok(Ok(response)) => tracing::info!("{}", response.message),
+                Ok(Err(e)) => tracing::error!("{}", e),
+                Err(e) => tracing::error!("发送命令失败: {}", e),
+            }
+        }
+    });
+
+    println!("⌨️  全局快捷键监听已启动（{}）", config.hotkey.backend);
+    input::run_hotkeys(&config.hotkey, tx).await
+}
+
+/// 听写历史子命令
+fn run_history(config: &Config, command: HistoryCommand) -> linux_voice_input_rs::Result<()> {
+    let store = HistoryStore::open(config.history.db_path())?;
+
+    let entries = match command {
+        HistoryCommand::List { limit } => store.recent(limit)?,
+        HistoryCommand::Search { keyword, limit } => store.search(&keyword, limit)?,
+        HistoryCommand::Show { id } => {
+            let entry = find_history(&store, id)?;
+            println!("📝 记录 #{}", entry.id);
+            println!("  - 时间: {}", entry.created_at.format("%Y-%m-%d %H:%M:%S"));
+            println!("  - 引擎: {}", entry.engine);
+            println!("  - 语言: {}", entry.language);
+            println!("  - 时长: {:.1} 秒", entry.duration);
+            println!("  - 输出: {}", entry.output);
+            println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+            print_result(config, &entry.text, &entry.timed_segments());
+            return Ok(());
+        }
+        HistoryCommand::Copy { id } => {
+            let entry = find_history(&store, id)?;
+            let mut clipboard = ClipboardOutput::new()?;
+            clipboard.copy(&entry.text)?;
+            println!("✅ 已将记录 #{} 复制到剪贴板", entry.id);
+            return Ok(());
+        }
+        HistoryCommand::Export {
+            ids,
+            format,
+            last,
+            output,
+        } => {
+            let entries = if ids.is_empty() {
+                // 按时间先后排列
+                let mut entries = store.recent(last)?;
+                entries.reverse();
+                entries
+            } else {
+                ids.iter()
+                    .map(|id| find_history(&store, *id))
+                    .collect::<linux_voice_input_rs::Result<Vec<_>>>()?
+            };
+            if entries.is_empty() {
+                return Err(VoiceInputError::History("没有可导出的记录".to_string()));
+            }
+
+            let content = export::render(&entries, format);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, content)?;
+                    println!("✅ 已导出 {} 条记录到 {}", entries.len(), path.display());
+                }
+                None => print!("{}", content),
+            }
+            return Ok(());
+        }
+    };
+
+    if entries.is_empty() {
+        println!("没有历史记录");
+    }
+    for entry in entries {
+        println!(
+            "#{:<5} {}  {:>5.1}s  {}",
+            entry.id,
+            entry.created_at.format("%Y-%m-%d %H:%M"),
+            entry.duration,
+            entry.preview(60)
+        );
+    }
     Ok(())
 }
+
+fn find_history(store: &HistoryStore, id: i64) -> linux_voice_input_rs::Result<HistoryEntry> {
+    store
+        .get(id)?
+        .ok_or_else(|| VoiceInputError::History(format!("记录 #{} 不存在", id)))
+}
@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+
+use crate::config::CodeConfig;
+use crate::processing::phrase::match_phrase;
+use crate::processing::{Pipeline, TextProcessor, Trim};
+
+/// 命名风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Case {
+    /// userName
+    Camel,
+    /// UserName
+    Pascal,
+    /// user_name
+    Snake,
+    /// USER_NAME
+    Constant,
+    /// user-name
+    Kebab,
+}
+
+/// 命名风格命令；后面没有单词时输出括号中的符号（"下划线" 单独说是 `_`）
+const CASES: &[(&str, Case, &str)] = &[
+    ("驼峰", Case::Camel, ""),
+    ("小驼峰", Case::Camel, ""),
+    ("camel case", Case::Camel, ""),
+    ("camel", Case::Camel, ""),
+    ("大驼峰", Case::Pascal, ""),
+    ("帕斯卡", Case::Pascal, ""),
+    ("pascal case", Case::Pascal, ""),
+    ("pascal", Case::Pascal, ""),
+    ("下划线", Case::Snake, "_"),
+    ("snake case", Case::Snake, "_"),
+    ("snake", Case::Snake, "_"),
+    ("常量", Case::Constant, ""),
+    ("constant", Case::Constant, ""),
+    ("短横线", Case::Kebab, "-"),
+    ("kebab case", Case::Kebab, "-"),
+    ("kebab", Case::Kebab, "-"),
+];
+
+/// 符号两侧的空格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    /// 不加空格
+    None,
+    /// 与单词之间加空格
+    Soft,
+    /// 总是加空格
+    Force,
+}
+
+use Space::{Force, None as Tight, Soft};
+
+/// 口述符号：说法、符号、左侧空格、右侧空格
+const SYMBOLS: &[(&str, &str, Space, Space)] = &[
+    ("左括号", "(", Tight, Tight),
+    ("右括号", ")", Tight, Soft),
+    ("左方括号", "[", Tight, Tight),
+    ("右方括号", "]", Tight, Soft),
+    ("左花括号", "{", Force, Tight),
+    ("左大括号", "{", Force, Tight),
+    ("右花括号", "}", Tight, Soft),
+    ("右大括号", "}", Tight, Soft),
+    ("左尖括号", "<", Tight, Tight),
+    ("右尖括号", ">", Tight, Soft),
+    ("箭头", "->", Force, Force),
+    ("胖箭头", "=>", Force, Force),
+    ("双箭头", "=>", Force, Force),
+    ("等于", "=", Force, Force),
+    ("等等于", "==", Force, Force),
+    ("双等于", "==", Force, Force),
+    ("不等于", "!=", Force, Force),
+    ("小于", "<", Force, Force),
+    ("大于", ">", Force, Force),
+    ("小于等于", "<=", Force, Force),
+    ("大于等于", ">=", Force, Force),
+    ("加号", "+", Force, Force),
+    ("减号", "-", Force, Force),
+    ("乘号", "*", Force, Force),
+    ("星号", "*", Tight, Tight),
+    ("斜杠", "/", Tight, Tight),
+    ("反斜杠", "\\", Tight, Tight),
+    ("并且", "&&", Force, Force),
+    ("或者", "||", Force, Force),
+    ("点", ".", Tight, Tight),
+    ("逗号", ",", Tight, Force),
+    ("分号", ";", Tight, Force),
+    ("冒号", ":", Tight, Force),
+    ("双冒号", "::", Tight, Tight),
+    ("双引号", "\"", Tight, Tight),
+    ("单引号", "'", Tight, Tight),
+    ("反引号", "`", Tight, Tight),
+    ("感叹号", "!", Tight, Tight),
+    ("问号", "?", Tight, Tight),
+    ("井号", "#", Tight, Tight),
+    ("艾特", "@", Tight, Tight),
+    ("美元符", "$", Tight, Tight),
+    ("百分号", "%", Tight, Tight),
+    ("和号", "&", Tight, Tight),
+    ("竖线", "|", Tight, Tight),
+    ("波浪号", "~", Tight, Tight),
+    ("空格", " ", Tight, Tight),
+    ("换行", "\n", Tight, Tight),
+    ("缩进", "\t", Tight, Tight),
+    ("open paren", "(", Tight, Tight),
+    ("close paren", ")", Tight, Soft),
+    ("open bracket", "[", Tight, Tight),
+    ("close bracket", "]", Tight, Soft),
+    ("open brace", "{", Force, Tight),
+    ("close brace", "}", Tight, Soft),
+    ("arrow", "->", Force, Force),
+    ("fat arrow", "=>", Force, Force),
+    ("equals", "=", Force, Force),
+    ("double equals", "==", Force, Force),
+    ("not equals", "!=", Force, Force),
+    ("less than", "<", Force, Force),
+    ("greater than", ">", Force, Force),
+    ("plus", "+", Force, Force),
+    ("minus", "-", Force, Force),
+    ("times", "*", Force, Force),
+    ("star", "*", Tight, Tight),
+    ("slash", "/", Tight, Tight),
+    ("backslash", "\\", Tight, Tight),
+    ("and and", "&&", Force, Force),
+    ("or or", "||", Force, Force),
+    ("dot", ".", Tight, Tight),
+    ("comma", ",", Tight, Force),
+    ("semicolon", ";", Tight, Force),
+    ("colon", ":", Tight, Force),
+    ("double colon", "::", Tight, Tight),
+    ("quote", "\"", Tight, Tight),
+    ("single quote", "'", Tight, Tight),
+    ("backtick", "`", Tight, Tight),
+    ("bang", "!", Tight, Tight),
+    ("question mark", "?", Tight, Tight),
+    ("hash", "#", Tight, Tight),
+    ("at sign", "@", Tight, Tight),
+    ("dollar", "$", Tight, Tight),
+    ("percent", "%", Tight, Tight),
+    ("ampersand", "&", Tight, Tight),
+    ("pipe", "|", Tight, Tight),
+    ("tilde", "~", Tight, Tight),
+    ("space", " ", Tight, Tight),
+    ("new line", "\n", Tight, Tight),
+    ("newline", "\n", Tight, Tight),
+    ("tab", "\t", Tight, Tight),
+];
+
+/// 转义词：说 "字面 点" 输出 "点" 本身
+const ESCAPE: &[&str] = &["字面", "literal"];
+
+/// 识别结果中的标点（代码模式关闭了引擎标点，这里兜底去掉）
+const PUNCTUATION: &[char] = &['，', '。', '？', '！', '、', '；', '：'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Case(Case, &'static str),
+    Symbol(&'static str, Space, Space),
+    Custom(usize),
+    Escape,
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    Word(&'a str),
+    Command(Kind, &'a str),
+}
+
+/// 代码听写：命名风格命令和口述符号
+///
+/// "驼峰 user name" → `userName`，"下划线 max retry count" → `max_retry_count`，
+/// "左括号"、"箭头" 等输出对应符号，运算符两侧自动加空格。
+pub struct CodeGrammar {
+    /// 用户自定义符号（说法 → 文本）
+    custom: Vec<(String, String)>,
+}
+
+impl CodeGrammar {
+    pub fn new(symbols: &BTreeMap<String, String>) -> Self {
+        Self {
+            custom: symbols.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        }
+    }
+
+    /// 在字节位置 `at` 处匹配最长的命令
+    fn match_command(&self, text: &str, at: usize) -> Option<(Kind, usize)> {
+        let phrases = CASES
+            .iter()
+            .map(|(phrase, case, fallback)| (*phrase, Kind::Case(*case, fallback)))
+            .chain(SYMBOLS.iter().map(|(phrase, symbol, before, after)| (*phrase, Kind::Symbol(symbol, *before, *after))))
+            .chain(ESCAPE.iter().map(|phrase| (*phrase, Kind::Escape)))
+            .chain(self.custom.iter().enumerate().map(|(i, (phrase, _))| (phrase.as_str(), Kind::Custom(i))));
+
+        phrases
+            .filter(|(phrase, _)| !phrase.is_empty() && match_phrase(text, at, phrase).is_some())
+            .max_by_key(|(phrase, _)| phrase.len())
+            .map(|(phrase, kind)| (kind, phrase.len()))
+    }
+
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        let mut i = 0;
+        let push_words = |tokens: &mut Vec<Token<'a>>, words: &'a str| {
+            tokens.extend(
+                words
+                    .split(|c: char| c.is_whitespace() || PUNCTUATION.contains(&c))
+                    .filter(|word| !word.is_empty())
+                    .map(Token::Word),
+            );
+        };
+        while i < text.len() {
+            match self.match_command(text, i) {
+                Some((kind, len)) => {
+                    push_words(&mut tokens, &text[start..i]);
+                    tokens.push(Token::Command(kind, &text[i..i + len]));
+                    i += len;
+                    start = i;
+                }
+                None => i += text[i..].chars().next().map_or(1, char::len_utf8),
+            }
+        }
+        push_words(&mut tokens, &text[start..]);
+        tokens
+    }
+}
+
+impl TextProcessor for CodeGrammar {
+    fn name(&self) -> &'static str {
+        "code"
+    }
+
+    fn process(&self, text: &str) -> String {
+        let tokens = self.tokenize(text);
+        let mut output = Output::default();
+        let mut i = 0;
+        while i < tokens.len() {
+            match &tokens[i] {
+                Token::Word(word) => output.push(word, Soft, Soft),
+                Token::Command(Kind::Escape, phrase) => match tokens.get(i + 1) {
+                    Some(Token::Command(_, literal)) => {
+                        output.push(literal, Soft, Soft);
+                        i += 1;
+                    }
+                    _ => output.push(phrase, Soft, Soft),
+                },
+                Token::Command(Kind::Symbol(symbol, before, after), _) => output.push(symbol, *before, *after),
+                Token::Command(Kind::Custom(index), _) => output.push(&self.custom[*index].1, Soft, Soft),
+                Token::Command(Kind::Case(case, fallback), _) => {
+                    let words: Vec<&str> = tokens[i + 1..]
+                        .iter()
+                        .map_while(|token| match token {
+                            Token::Word(word) => Some(*word),
+                            Token::Command(..) => None,
+                        })
+                        .collect();
+                    if words.is_empty() {
+                        output.push(fallback, Tight, Tight);
+                    } else {
+                        output.push(&apply_case(*case, &words), Soft, Soft);
+                        i += words.len();
+                    }
+                }
+            }
+            i += 1;
+        }
+        output.text
+    }
+}
+
+/// 按符号的空格规则拼接
+#[derive(Default)]
+struct Output {
+    text: String,
+    /// 上一段右侧的空格规则
+    after: Option<Space>,
+}
+
+impl Output {
+    fn push(&mut self, piece: &str, before: Space, after: Space) {
+        if piece.is_empty() {
+            return;
+        }
+        if let Some(prev) = self.after {
+            let space = prev == Force && before != Tight
+                || before == Force && prev != Tight
+                || prev == Soft && before == Soft
+                || (prev == Force && before == Tight && is_open(piece));
+            if space && !self.text.ends_with([' ', '\n', '\t']) {
+                self.text.push(' ');
+            }
+        }
+        self.text.push_str(piece);
+        self.after = Some(after);
+    }
+}
+
+/// 左括号前面是运算符时仍加空格（`x = (`）
+fn is_open(piece: &str) -> bool {
+    matches!(piece, "(" | "[")
+}
+
+/// 把单词拼接为指定的命名风格
+fn apply_case(case: Case, words: &[&str]) -> String {
+    let words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    };
+    match case {
+        Case::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| if i == 0 { word.clone() } else { capitalize(word) })
+            .collect(),
+        Case::Pascal => words.iter().map(capitalize).collect::<String>(),
+        Case::Snake => words.join("_"),
+        Case::Constant => words.join("_").to_uppercase(),
+        Case::Kebab => words.join("-"),
+    }
+}
+
+/// 代码模式使用的处理链（替代 `[processing]` 中的步骤）
+pub fn pipeline(config: &CodeConfig) -> Pipeline {
+    let mut pipeline = Pipeline::default();
+    pipeline.push(Trim);
+    pipeline.push(CodeGrammar::new(&config.symbols));
+    pipeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(text: &str) -> String {
+        CodeGrammar::new(&BTreeMap::new()).process(text)
+    }
+
+    #[test]
+    fn test_casing() {
+        assert_eq!(code("驼峰 user name"), "userName");
+        assert_eq!(code("下划线 max retry count"), "max_retry_count");
+        assert_eq!(code("大驼峰user service"), "UserService");
+        assert_eq!(code("常量 max size"), "MAX_SIZE");
+        assert_eq!(code("kebab case Main Menu"), "main-menu");
+        assert_eq!(code("camel Get User Name"), "getUserName");
+    }
+
+    #[test]
+    fn test_symbols() {
+        assert_eq!(code("驼峰 get user 左括号右括号"), "getUser()");
+        assert_eq!(code("fn main 左括号 右括号 箭头 result 左花括号"), "fn main() -> result {");
+        assert_eq!(code("let 下划线 retry count 等于 左括号 a 逗号 b 右括号 分号"), "let retry_count = (a, b);");
+        assert_eq!(code("self 点 驼峰 user name"), "self.userName");
+        assert_eq!(code("std 双冒号 io"), "std::io");
+        assert_eq!(code("if x not equals none colon"), "if x != none:");
+        assert_eq!(code("items 点 map 左括号 x 胖箭头 x 乘号 2 右括号"), "items.map(x => x * 2)");
+    }
+
+    #[test]
+    fn test_fallback_and_escape() {
+        // 单独的 "下划线" 是符号
+        assert_eq!(code("下划线 左括号"), "_(");
+        assert_eq!(code("print 左括号 双引号 字面 点 双引号 右括号"), "print(\"点\")");
+        assert_eq!(code("literal comma"), "comma");
+    }
+
+    #[test]
+    fn test_custom_symbols() {
+        let symbols = BTreeMap::from([("打印宏".to_string(), "println!".to_string())]);
+        let grammar = CodeGrammar::new(&symbols);
+        assert_eq!(grammar.process("打印宏 左括号 右括号"), "println!()");
+    }
+
+    #[test]
+    fn test_engine_punctuation_removed() {
+        assert_eq!(code("驼峰 user name。"), "userName");
+    }
+}
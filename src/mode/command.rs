@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::Local;
+use regex::Regex;
+use zbus::zvariant::{StructureBuilder, Value};
+use zbus::Connection;
+
+use crate::config::{CommandMatch, DbusArg, DbusCall, VoiceCommand};
+use crate::output::DesktopNotifier;
+use crate::processing::vocabulary::pinyin::{syllable_score, PinyinTable};
+use crate::utils::{Result, VoiceInputError};
+use crate::Config;
+
+/// 一条说法的匹配规则
+enum Matcher {
+    Exact(String),
+    Fuzzy(String),
+    Regex(Regex),
+}
+
+/// 配置中的全部语音命令
+pub struct CommandSet<'a> {
+    /// （命令序号，说法，匹配规则）
+    phrases: Vec<(usize, &'a str, Matcher)>,
+    commands: &'a [VoiceCommand],
+    table: PinyinTable,
+    threshold: f64,
+}
+
+/// 匹配到的命令
+pub struct Matched<'a> {
+    pub command: &'a VoiceCommand,
+    pub phrase: &'a str,
+    /// 正则捕获的参数（序号或组名 → 文本）
+    pub args: HashMap<String, String>,
+}
+
+/// 要执行的动作（参数已替换）
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Shell(String),
+    Dbus(DbusCall),
+}
+
+impl<'a> CommandSet<'a> {
+    /// 检查并编译所有命令（正则无效、缺少动作时报错）
+    pub fn new(commands: &'a [VoiceCommand], threshold: f64) -> Result<Self> {
+        let mut phrases = Vec::new();
+        for (index, command) in commands.iter().enumerate() {
+            let name = command.phrases.first().map(String::as_str).unwrap_or_default();
+            if command.run.is_some() == command.dbus.is_some() {
+                return Err(VoiceInputError::Config(format!(
+                    "语音命令 {:?} 需要设置 run 或 dbus 中的一个",
+                    name
+                )));
+            }
+            for phrase in command.phrases.iter().filter(|phrase| !phrase.trim().is_empty()) {
+                let matcher = match command.matching {
+                    CommandMatch::Exact => Matcher::Exact(normalize(phrase)),
+                    CommandMatch::Fuzzy => Matcher::Fuzzy(normalize(phrase)),
+                    CommandMatch::Regex => Matcher::Regex(Regex::new(&format!("^(?:{})$", phrase)).map_err(|e| {
+                        VoiceInputError::Config(format!("语音命令 {:?} 不是有效的正则表达式: {}", phrase, e))
+                    })?),
+                };
+                phrases.push((index, phrase.as_str(), matcher));
+            }
+        }
+        Ok(Self {
+            phrases,
+            commands,
+            table: PinyinTable::default(),
+            threshold,
+        })
+    }
+
+    /// 查找命令：完全相同的说法优先，其次是正则，最后是最相近的模糊说法
+    pub fn find(&self, text: &str) -> Option<Matched<'a>> {
+        let normalized = normalize(text);
+        let matched = |index: usize, phrase: &'a str, args| Matched {
+            command: &self.commands[index],
+            phrase,
+            args,
+        };
+
+        let exact = self.phrases.iter().find(|(_, _, matcher)| match matcher {
+            Matcher::Exact(expected) | Matcher::Fuzzy(expected) => *expected == normalized,
+            Matcher::Regex(_) => false,
+        });
+        if let Some((index, phrase, _)) = exact {
+            return Some(matched(*index, phrase, HashMap::new()));
+        }
+
+        let sentence = text.trim().trim_end_matches(['。', '.', '！', '!', '？', '?']).trim();
+        for (index, phrase, matcher) in &self.phrases {
+            let Matcher::Regex(regex) = matcher else { continue };
+            let Some(captures) = regex.captures(sentence) else { continue };
+            let mut args = HashMap::new();
+            for (i, name) in regex.capture_names().enumerate() {
+                let Some(value) = captures.get(i) else { continue };
+                let value = value.as_str().trim().to_string();
+                if let Some(name) = name {
+                    args.insert(name.to_string(), value.clone());
+                }
+                args.insert(i.to_string(), value);
+            }
+            return Some(matched(*index, phrase, args));
+        }
+
+        self.phrases
+            .iter()
+            .filter_map(|(index, phrase, matcher)| match matcher {
+                Matcher::Fuzzy(expected) => Some((self.similarity(&normalized, expected), *index, *phrase)),
+                _ => None,
+            })
+            .filter(|(score, _, _)| *score >= self.threshold)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, index, phrase)| matched(index, phrase, HashMap::new()))
+    }
+
+    /// 相似度：按字的编辑距离，字数相同时也按拼音比较（容忍同音字）
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        let edit = 1.0 - levenshtein(&a, &b) as f64 / a.len().max(b.len()) as f64;
+        if a.len() != b.len() {
+            return edit;
+        }
+        let pinyin = a
+            .iter()
+            .zip(&b)
+            .map(|(x, y)| {
+                if x == y {
+                    return 1.0;
+                }
+                match (self.table.readings(*x), self.table.readings(*y)) {
+                    (Some(xs), Some(ys)) => xs
+                        .iter()
+                        .flat_map(|x| ys.iter().map(move |y| syllable_score(x, y)))
+                        .fold(0.0, f64::max),
+                    _ => 0.0,
+                }
+            })
+            .sum::<f64>()
+            / a.len() as f64;
+        edit.max(pinyin)
+    }
+}
+
+impl Matched<'_> {
+    /// 替换参数后的动作
+    pub fn action(&self) -> Action {
+        match (&self.command.run, &self.command.dbus) {
+            (Some(run), _) => Action::Shell(substitute(run, &self.args, true)),
+            (None, Some(dbus)) => {
+                let mut call = dbus.clone();
+                for arg in &mut call.args {
+                    if let DbusArg::Str(value) = arg {
+                        *value = substitute(value, &self.args, false);
+                    }
+                }
+                Action::Dbus(call)
+            }
+            (None, None) => unreachable!("CommandSet::new 已检查"),
+        }
+    }
+}
+
+impl Action {
+    /// 日志和通知中显示的描述
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Shell(command) => command.clone(),
+            Self::Dbus(call) => format!(
+                "dbus {} {} {}.{}({})",
+                call.bus,
+                call.service,
+                call.interface,
+                call.method,
+                call.args
+                    .iter()
+                    .map(|arg| match arg {
+                        DbusArg::Bool(value) => value.to_string(),
+                        DbusArg::Int(value) => value.to_string(),
+                        DbusArg::Str(value) => format!("{:?}", value),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// 执行：shell 命令在后台运行，不等待结束；D-Bus 调用等待返回
+    pub async fn execute(&self) -> Result<()> {
+        match self {
+            Self::Shell(command) => {
+                let mut child = tokio::process::Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .stdin(std::process::Stdio::null())
+                    .spawn()
+                    .map_err(|e| VoiceInputError::Command(format!("启动 {:?} 失败: {}", command, e)))?;
+                let command = command.clone();
+                tokio::spawn(async move {
+                    match child.wait().await {
+                        Ok(status) if !status.success() => tracing::warn!("命令 {:?} 退出: {}", command, status),
+                        Ok(_) => tracing::debug!("命令 {:?} 已结束", command),
+                        Err(e) => tracing::warn!("等待命令 {:?} 失败: {}", command, e),
+                    }
+                });
+                Ok(())
+            }
+            Self::Dbus(call) => call_dbus(call).await,
+        }
+    }
+}
+
+/// 在命令模式下处理一次识别结果：匹配、确认、执行并记录日志
+pub async fn run_command(config: &Config, text: &str, notifier: Option<&DesktopNotifier>) -> Result<()> {
+    let commands = CommandSet::new(&config.commands, config.command.threshold)?;
+    let matched = commands
+        .find(text)
+        .ok_or_else(|| VoiceInputError::Command(format!("没有匹配 \"{}\" 的命令", text)))?;
+    let action = matched.action();
+    let description = action.describe();
+    let log = config.command.log_path();
+
+    if matched.command.dangerous {
+        let timeout = Duration::from_secs(config.command.confirm_timeout);
+        if !confirm(matched.phrase, &description, timeout, notifier).await? {
+            println!("🚫 已取消: {}", description);
+            log_execution(&log, text, &description, "cancelled");
+            return Ok(());
+        }
+    }
+
+    println!("▶️  {}", description);
+    match action.execute().await {
+        Ok(()) => {
+            tracing::info!("已执行语音命令 {:?}: {}", matched.phrase, description);
+            log_execution(&log, text, &description, "ok");
+            if let Some(notifier) = notifier {
+                if let Err(e) = notifier.command_executed(matched.phrase, &description).await {
+                    tracing::warn!("{}", e);
+                }
+            }
+            Ok(())
+        }
+        Err(e) => {
+            log_execution(&log, text, &description, &format!("error: {}", e));
+            Err(e)
+        }
+    }
+}
+
+/// 危险命令的确认：优先用带按钮的桌面通知，通知不可用时在终端中询问
+async fn confirm(
+    phrase: &str,
+    description: &str,
+    timeout: Duration,
+    notifier: Option<&DesktopNotifier>,
+) -> Result<bool> {
+    let notifier = match notifier {
+        Some(notifier) => Ok(notifier.clone()),
+        None => DesktopNotifier::connect().await,
+    };
+    match notifier {
+        Ok(notifier) => {
+            println!("⚠️  等待确认（桌面通知）: {}", description);
+            notifier.confirm(&format!("⚠️ 确认执行 \"{}\"？", phrase), description, timeout).await
+        }
+        Err(e) if std::io::stdin().is_terminal() => {
+            tracing::debug!("{}", e);
+            print!("⚠️  确认执行 \"{}\"？\n   {}\n[y/N] ", phrase, description);
+            std::io::stdout().flush()?;
+            let answer = tokio::task::spawn_blocking(|| {
+                let mut line = String::new();
+                std::io::stdin().read_line(&mut line).map(|_| line)
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("读取确认失败: {}", e))??;
+            Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes" | "是"))
+        }
+        Err(e) => Err(VoiceInputError::Command(format!("无法确认危险命令: {}", e))),
+    }
+}
+
+async fn call_dbus(call: &DbusCall) -> Result<()> {
+    let dbus_error = |e: zbus::Error| VoiceInputError::Command(format!("D-Bus 调用 {}.{} 失败: {}", call.interface, call.method, e));
+    let connection = match call.bus.as_str() {
+        "session" => Connection::session().await,
+        "system" => Connection::system().await,
+        other => {
+            return Err(VoiceInputError::Config(format!(
+                "D-Bus 总线只能是 session 或 system: {}",
+                other
+            )))
+        }
+    }
+    .map_err(dbus_error)?;
+
+    let (service, path, interface, method) = (
+        call.service.as_str(),
+        call.path.as_str(),
+        call.interface.as_str(),
+        call.method.as_str(),
+    );
+    if call.args.is_empty() {
+        connection
+            .call_method(Some(service), path, Some(interface), method, &())
+            .await
+            .map_err(dbus_error)?;
+    } else {
+        let body = call
+            .args
+            .iter()
+            .fold(StructureBuilder::new(), |builder, arg| {
+                builder.append_field(match arg {
+                    DbusArg::Bool(value) => Value::Bool(*value),
+                    DbusArg::Int(value) => Value::I32(*value),
+                    DbusArg::Str(value) => Value::from(value.as_str()),
+                })
+            })
+            .build()
+            .map_err(|e| dbus_error(e.into()))?;
+        connection
+            .call_method(Some(service), path, Some(interface), method, &body)
+            .await
+            .map_err(dbus_error)?;
+    }
+    Ok(())
+}
+
+/// 追加一行执行日志：时间、识别结果、动作、结果
+fn log_execution(path: &Path, text: &str, action: &str, status: &str) {
+    let line = format!(
+        "{}\t{}\t{}\t{}\n",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        text.replace(['\t', '\n'], " "),
+        action.replace(['\t', '\n'], " "),
+        status
+    );
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::OpenOptions::new().create(true).append(true).open(path))
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+    if let Err(e) = written {
+        tracing::warn!("写入命令日志 {} 失败: {}", path.display(), e);
+    }
+}
+
+/// 比较用的形式：去掉标点和空白，英文转小写
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// 把 `{1}`、`{name}` 替换为参数（未知的占位符原样保留）；`quote` 时按 shell 单引号转义
+fn substitute(template: &str, args: &HashMap<String, String>, quote: bool) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .find('}')
+            .and_then(|end| args.get(&after[..end]).map(|value| (end, value)));
+        match value {
+            Some((end, value)) => {
+                if quote {
+                    result.push_str(&shell_quote(value));
+                } else {
+                    result.push_str(value);
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, y) in b.iter().enumerate() {
+            let cost = usize::from(x != y);
+            current.push((previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(phrases: &[&str], matching: CommandMatch, run: &str) -> VoiceCommand {
+        VoiceCommand {
+            phrases: phrases.iter().map(|p| p.to_string()).collect(),
+            matching,
+            run: Some(run.to_string()),
+            dbus: None,
+            dangerous: false,
+        }
+    }
+
+    fn commands() -> Vec<VoiceCommand> {
+        vec![
+            command(&["打开终端", "open terminal"], CommandMatch::Fuzzy, "gnome-terminal"),
+            command(&["锁屏"], CommandMatch::Exact, "loginctl lock-session"),
+            command(&["发布日报(?P<title>.*)"], CommandMatch::Regex, "publish-report {title}"),
+            command(&[r"音量调到(\d+)"], CommandMatch::Regex, "pactl set-sink-volume @DEFAULT_SINK@ {1}%"),
+        ]
+    }
+
+    #[test]
+    fn test_exact_and_fuzzy() {
+        let commands = commands();
+        let set = CommandSet::new(&commands, 0.8).unwrap();
+        assert_eq!(set.find("锁屏。").unwrap().phrase, "锁屏");
+        assert_eq!(set.find("Open terminal.").unwrap().phrase, "open terminal");
+        // 同音误识别
+        assert_eq!(set.find("打开中端").unwrap().phrase, "打开终端");
+        // 精确匹配的命令不做模糊匹配
+        assert!(set.find("锁平").is_none());
+        assert!(set.find("今天天气很好").is_none());
+    }
+
+    #[test]
+    fn test_regex_arguments() {
+        let commands = commands();
+        let set = CommandSet::new(&commands, 0.8).unwrap();
+        let matched = set.find("音量调到30。").unwrap();
+        assert_eq!(
+            matched.action(),
+            Action::Shell("pactl set-sink-volume @DEFAULT_SINK@ '30'%".to_string())
+        );
+        // 参数按 shell 单引号转义
+        let matched = set.find("发布日报 it's done").unwrap();
+        assert_eq!(matched.action(), Action::Shell(r"publish-report 'it'\''s done'".to_string()));
+    }
+
+    #[test]
+    fn test_substitute_keeps_unknown_placeholders() {
+        let args = HashMap::from([("1".to_string(), "a b".to_string())]);
+        assert_eq!(substitute("awk '{print}' {1}", &args, true), "awk '{print}' 'a b'");
+        assert_eq!(substitute("{1}{2}", &args, false), "a b{2}");
+    }
+
+    #[test]
+    fn test_invalid_commands() {
+        let invalid = [command(&["(未闭合"], CommandMatch::Regex, "true")];
+        assert!(matches!(CommandSet::new(&invalid, 0.8), Err(VoiceInputError::Config(_))));
+
+        let mut both = command(&["锁屏"], CommandMatch::Exact, "true");
+        both.dbus = Some(DbusCall {
+            bus: "session".to_string(),
+            service: "org.gnome.ScreenSaver".to_string(),
+            path: "/org/gnome/ScreenSaver".to_string(),
+            interface: "org.gnome.ScreenSaver".to_string(),
+            method: "Lock".to_string(),
+            args: Vec::new(),
+        });
+        assert!(CommandSet::new(&[both], 0.8).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+/// 最多保留的历史版本数
+pub const MAX_REVISIONS: usize = 50;
+
+/// 句末标点（"删除最后一句" 按这些字符分句）
+const SENTENCE_END: &[char] = &['。', '！', '？', '；', '.', '!', '?', ';', '\n'];
+
+/// 整句匹配的固定编辑命令
+const COMMANDS: &[(&str, Edit)] = &[
+    ("撤销", Edit::Undo),
+    ("撤回", Edit::Undo),
+    ("undo", Edit::Undo),
+    ("删除最后一句", Edit::DeleteLastSentence),
+    ("删掉最后一句", Edit::DeleteLastSentence),
+    ("删除最后一句话", Edit::DeleteLastSentence),
+    ("删掉最后一句话", Edit::DeleteLastSentence),
+    ("delete last sentence", Edit::DeleteLastSentence),
+    ("delete the last sentence", Edit::DeleteLastSentence),
+    ("全部大写", Edit::Uppercase),
+    ("全大写", Edit::Uppercase),
+    ("all caps", Edit::Uppercase),
+    ("uppercase", Edit::Uppercase),
+    ("全部小写", Edit::Lowercase),
+    ("全小写", Edit::Lowercase),
+    ("lowercase", Edit::Lowercase),
+];
+
+/// 替换命令：开头、中间连接词（"把 X 改成 Y"、"replace X with Y"）
+const REPLACE: &[(&str, &[&str])] = &[
+    ("把", &["改成", "改为", "换成", "替换成", "替换为"]),
+    ("将", &["改成", "改为", "换成", "替换成", "替换为"]),
+    ("replace ", &[" with "]),
+    ("change ", &[" to "]),
+];
+
+/// 修改上一次结果的语音编辑命令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// 把 X 改成 Y（所有出现的地方）
+    Replace { from: String, to: String },
+    /// 删除最后一句
+    DeleteLastSentence,
+    /// 撤销上一次修改
+    Undo,
+    /// 全部大写
+    Uppercase,
+    /// 全部小写
+    Lowercase,
+}
+
+impl Edit {
+    /// 整句识别结果是编辑命令时解析出来（忽略末尾标点和大小写）
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_end_matches(SENTENCE_END).trim();
+        let lower = text.to_ascii_lowercase();
+        if let Some((_, edit)) = COMMANDS.iter().find(|(phrase, _)| *phrase == lower) {
+            return Some(edit.clone());
+        }
+        let unquote = |s: &str| s.trim().trim_matches(['"', '“', '”', '「', '」', '\'']).trim().to_string();
+        REPLACE.iter().find_map(|(prefix, separators)| {
+            // 英文命令忽略大小写（只转换 ASCII，字节位置不变）
+            let rest = lower.strip_prefix(prefix).map(|_| &text[prefix.len()..])?;
+            let lower_rest = &lower[prefix.len()..];
+            let (index, separator) = separators
+                .iter()
+                .filter_map(|separator| lower_rest.find(separator).map(|index| (index, separator.len())))
+                .min()?;
+            let (from, to) = (unquote(&rest[..index]), unquote(&rest[index + separator..]));
+            (!from.is_empty()).then_some(Self::Replace { from, to })
+        })
+    }
+
+    /// 作用于上一次结果（撤销由 [`Revisions`] 处理）；无法应用时返回 None
+    fn apply(&self, text: &str) -> Option<String> {
+        match self {
+            Self::Replace { from, to } => text.contains(from.as_str()).then(|| text.replace(from.as_str(), to)),
+            Self::DeleteLastSentence => Some(delete_last_sentence(text)),
+            Self::Uppercase => Some(text.to_uppercase()),
+            Self::Lowercase => Some(text.to_lowercase()),
+            Self::Undo => None,
+        }
+    }
+}
+
+/// 最近几次结果（旧的在前），编辑命令作用于最后一个
+#[derive(Debug, Default)]
+pub struct Revisions {
+    texts: Vec<String>,
+}
+
+impl Revisions {
+    /// `texts` 按时间从旧到新排列
+    pub fn new(mut texts: Vec<String>) -> Self {
+        texts.retain(|text| !text.is_empty());
+        let skip = texts.len().saturating_sub(MAX_REVISIONS);
+        texts.drain(..skip);
+        Self { texts }
+    }
+
+    /// 上一次结果
+    pub fn current(&self) -> Option<&str> {
+        self.texts.last().map(String::as_str)
+    }
+
+    /// 记录一次新结果
+    pub fn push(&mut self, text: String) {
+        if text.is_empty() {
+            return;
+        }
+        if self.texts.len() >= MAX_REVISIONS {
+            self.texts.remove(0);
+        }
+        self.texts.push(text);
+    }
+
+    /// 应用编辑命令，返回修改后的结果；没有可修改的结果或命令不适用时返回 None
+    pub fn apply(&mut self, edit: &Edit) -> Option<String> {
+        if *edit == Edit::Undo {
+            if self.texts.len() < 2 {
+                return None;
+            }
+            self.texts.pop();
+            return self.current().map(str::to_string);
+        }
+        let edited = edit.apply(self.current()?)?;
+        self.texts.push(edited.clone());
+        Some(edited)
+    }
+}
+
+/// 删除最后一句（连同句末标点）
+fn delete_last_sentence(text: &str) -> String {
+    let body = text.trim_end().trim_end_matches(SENTENCE_END);
+    match body.rfind(SENTENCE_END) {
+        Some(index) => {
+            let end = index + body[index..].chars().next().map_or(1, char::len_utf8);
+            body[..end].trim_end().to_string()
+        }
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Edit::parse("撤销。"), Some(Edit::Undo));
+        assert_eq!(Edit::parse("Undo."), Some(Edit::Undo));
+        assert_eq!(Edit::parse("删除最后一句"), Some(Edit::DeleteLastSentence));
+        assert_eq!(Edit::parse("全部大写。"), Some(Edit::Uppercase));
+        assert_eq!(
+            Edit::parse("把“张三”改成李四。"),
+            Some(Edit::Replace {
+                from: "张三".to_string(),
+                to: "李四".to_string()
+            })
+        );
+        assert_eq!(
+            Edit::parse("Replace Monday with Tuesday."),
+            Some(Edit::Replace {
+                from: "Monday".to_string(),
+                to: "Tuesday".to_string()
+            })
+        );
+        assert_eq!(Edit::parse("我们明天撤销这个订单"), None);
+        assert_eq!(Edit::parse("今天天气很好"), None);
+    }
+
+    #[test]
+    fn test_apply_and_undo() {
+        let mut revisions = Revisions::new(vec!["明天上午开会。请张三准备材料。".to_string()]);
+        let edit = Edit::parse("把张三改成李四").unwrap();
+        assert_eq!(revisions.apply(&edit).as_deref(), Some("明天上午开会。请李四准备材料。"));
+        let edit = Edit::parse("删除最后一句").unwrap();
+        assert_eq!(revisions.apply(&edit).as_deref(), Some("明天上午开会。"));
+        assert_eq!(revisions.apply(&Edit::Undo).as_deref(), Some("明天上午开会。请李四准备材料。"));
+        assert_eq!(revisions.apply(&Edit::Undo).as_deref(), Some("明天上午开会。请张三准备材料。"));
+        // 没有更早的版本
+        assert_eq!(revisions.apply(&Edit::Undo), None);
+    }
+
+    #[test]
+    fn test_not_applicable() {
+        let mut revisions = Revisions::default();
+        assert_eq!(revisions.apply(&Edit::Uppercase), None);
+
+        revisions.push("hello world".to_string());
+        assert_eq!(revisions.apply(&Edit::Uppercase).as_deref(), Some("HELLO WORLD"));
+        // 上一次结果中没有要替换的内容
+        let edit = Edit::parse("把方案改成明天提交").unwrap();
+        assert_eq!(revisions.apply(&edit), None);
+        assert_eq!(revisions.current(), Some("HELLO WORLD"));
+    }
+
+    #[test]
+    fn test_delete_last_sentence() {
+        assert_eq!(delete_last_sentence("First one. Second one."), "First one.");
+        assert_eq!(delete_last_sentence("只有一句"), "");
+        assert_eq!(delete_last_sentence("第一句！第二句"), "第一句！");
+    }
+}
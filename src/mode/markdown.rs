@@ -0,0 +1,569 @@
+use std::path::PathBuf;
+
+use crate::processing::phrase::match_phrase;
+use crate::utils::Result;
+
+/// 口述的 Markdown 结构命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Heading(usize),
+    Bullet,
+    Numbered,
+    CodeStart,
+    CodeEnd,
+    Bold,
+    EndBold,
+    Italic,
+    EndItalic,
+    InlineCode,
+    EndInlineCode,
+    Link,
+    Url,
+    EndLink,
+}
+
+/// 命令说法（英文按整词、不区分大小写匹配）
+const COMMANDS: &[(&str, Command)] = &[
+    ("一级标题", Command::Heading(1)),
+    ("二级标题", Command::Heading(2)),
+    ("三级标题", Command::Heading(3)),
+    ("四级标题", Command::Heading(4)),
+    ("五级标题", Command::Heading(5)),
+    ("六级标题", Command::Heading(6)),
+    ("heading one", Command::Heading(1)),
+    ("heading two", Command::Heading(2)),
+    ("heading three", Command::Heading(3)),
+    ("heading four", Command::Heading(4)),
+    ("heading five", Command::Heading(5)),
+    ("heading six", Command::Heading(6)),
+    ("列表项", Command::Bullet),
+    ("list item", Command::Bullet),
+    ("bullet", Command::Bullet),
+    ("编号项", Command::Numbered),
+    ("numbered item", Command::Numbered),
+    ("结束代码块", Command::CodeEnd),
+    ("end code block", Command::CodeEnd),
+    ("代码块", Command::CodeStart),
+    ("code block", Command::CodeStart),
+    ("结束加粗", Command::EndBold),
+    ("end bold", Command::EndBold),
+    ("加粗", Command::Bold),
+    ("bold", Command::Bold),
+    ("结束斜体", Command::EndItalic),
+    ("end italic", Command::EndItalic),
+    ("斜体", Command::Italic),
+    ("italic", Command::Italic),
+    ("结束行内代码", Command::EndInlineCode),
+    ("end inline code", Command::EndInlineCode),
+    ("行内代码", Command::InlineCode),
+    ("inline code", Command::InlineCode),
+    ("结束链接", Command::EndLink),
+    ("end link", Command::EndLink),
+    ("链接", Command::Link),
+    ("link", Command::Link),
+    ("网址", Command::Url),
+    ("url", Command::Url),
+];
+
+/// 开始新文档的说法（只在一次听写的开头、且后面是标点或结尾时有效）
+const NEW_DOCUMENT: &[&str] = &["新文档", "new document"];
+
+/// 转义词：后面紧跟的命令按原文输出（"点击下面的字面链接" → "点击下面的链接"）
+const ESCAPE: &[&str] = &["字面", "literal"];
+
+/// 命令前后多余的标点和空白（"加粗，重要内容，结束加粗" → "**重要内容**"）
+const SEPARATORS: &[char] = &['，', ',', '、', '：', ':', '；', ';', ' ', '\t'];
+
+/// 标题、列表项末尾去掉的标点
+const TRAILING: &[char] = &['。', '.', '，', ',', '、', '；', ';', ' '];
+
+#[derive(Debug)]
+enum Token<'a> {
+    Text(&'a str),
+    /// 命令及其原文（代码块中按原文输出）
+    Command(Command, &'a str),
+}
+
+/// 把文本切分为普通文字和命令
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        // 转义：紧随其后的命令作为普通文字
+        if let Some(end) = ESCAPE.iter().find_map(|escape| match_phrase(text, i, escape)) {
+            let next = text.len() - text[end..].trim_start_matches(SEPARATORS).len();
+            if let Some((_, len)) = match_command(text, next) {
+                if start < i {
+                    tokens.push(Token::Text(&text[start..i]));
+                }
+                tokens.push(Token::Text(&text[next..next + len]));
+                i = next + len;
+                start = i;
+                continue;
+            }
+        }
+        match match_command(text, i) {
+            Some((command, len)) => {
+                if start < i {
+                    tokens.push(Token::Text(&text[start..i]));
+                }
+                tokens.push(Token::Command(command, &text[i..i + len]));
+                i += len;
+                start = i;
+            }
+            None => i += text[i..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if start < text.len() {
+        tokens.push(Token::Text(&text[start..]));
+    }
+    tokens
+}
+
+/// 在字节位置 `at` 处匹配最长的命令
+fn match_command(text: &str, at: usize) -> Option<(Command, usize)> {
+    COMMANDS
+        .iter()
+        .filter(|(phrase, _)| match_phrase(text, at, phrase).is_some())
+        .max_by_key(|(phrase, _)| phrase.len())
+        .map(|(phrase, command)| (*command, phrase.len()))
+}
+
+/// 块级元素
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Paragraph,
+    Heading(usize),
+    Bullet,
+    Numbered,
+    Code,
+}
+
+/// 行内标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Inline {
+    Bold,
+    Italic,
+    Code,
+    /// 链接文字（尚未说网址）
+    LinkText,
+    /// 链接网址
+    LinkUrl,
+}
+
+impl Inline {
+    fn close(&self) -> &'static str {
+        match self {
+            Inline::Bold => "**",
+            Inline::Italic => "*",
+            Inline::Code => "`",
+            Inline::LinkText => "]()",
+            Inline::LinkUrl => ")",
+        }
+    }
+}
+
+/// 逐个处理命令，生成 Markdown 块
+struct Renderer {
+    blocks: Vec<(Block, String)>,
+    block: Block,
+    content: String,
+    open: Vec<Inline>,
+    /// 下一段文字去掉开头的分隔符
+    trim_next: bool,
+}
+
+impl Renderer {
+    fn new() -> Self {
+        Self {
+            blocks: Vec::new(),
+            block: Block::Paragraph,
+            content: String::new(),
+            open: Vec::new(),
+            trim_next: false,
+        }
+    }
+
+    fn feed(&mut self, token: Token<'_>) {
+        if self.block == Block::Code {
+            match token {
+                Token::Command(Command::CodeEnd, _) => self.finish_block(Block::Paragraph),
+                Token::Text(text) | Token::Command(_, text) => self.content.push_str(text),
+            }
+            return;
+        }
+
+        match token {
+            Token::Text(text) => {
+                let text = if std::mem::take(&mut self.trim_next) {
+                    text.trim_start_matches(SEPARATORS)
+                } else {
+                    text
+                };
+                if self.open.last() == Some(&Inline::LinkUrl) {
+                    self.content.extend(text.chars().filter(|c| !c.is_whitespace()));
+                    return;
+                }
+                // 标题、列表项到句末为止，后面的文字另起一段
+                let single_line = matches!(self.block, Block::Heading(_) | Block::Bullet | Block::Numbered);
+                match sentence_end(text).filter(|_| single_line && self.open.is_empty()) {
+                    Some((end, next)) => {
+                        self.content.push_str(&text[..end]);
+                        self.finish_block(Block::Paragraph);
+                        self.feed(Token::Text(&text[next..]));
+                    }
+                    None => self.content.push_str(text),
+                }
+            }
+            Token::Command(command, _) => self.command(command),
+        }
+    }
+
+    fn command(&mut self, command: Command) {
+        match command {
+            Command::Heading(level) => self.finish_block(Block::Heading(level)),
+            Command::Bullet => self.finish_block(Block::Bullet),
+            Command::Numbered => self.finish_block(Block::Numbered),
+            Command::CodeStart => self.finish_block(Block::Code),
+            Command::CodeEnd => {}
+            Command::Bold => self.toggle(Inline::Bold, "**"),
+            Command::Italic => self.toggle(Inline::Italic, "*"),
+            Command::InlineCode => self.toggle(Inline::Code, "`"),
+            Command::EndBold => self.close(Inline::Bold),
+            Command::EndItalic => self.close(Inline::Italic),
+            Command::EndInlineCode => self.close(Inline::Code),
+            Command::Link => {
+                self.close_link();
+                self.content.push('[');
+                self.open.push(Inline::LinkText);
+                self.trim_next = true;
+            }
+            Command::Url => {
+                if let Some(last) = self.open.last_mut().filter(|inline| **inline == Inline::LinkText) {
+                    *last = Inline::LinkUrl;
+                    trim_end(&mut self.content, SEPARATORS);
+                    self.content.push_str("](");
+                    self.trim_next = true;
+                }
+            }
+            Command::EndLink => self.close_link(),
+        }
+    }
+
+    /// 打开行内标记；已经打开时视为结束
+    fn toggle(&mut self, inline: Inline, marker: &str) {
+        if self.open.contains(&inline) {
+            self.close(inline);
+        } else {
+            self.content.push_str(marker);
+            self.open.push(inline);
+            self.trim_next = true;
+        }
+    }
+
+    /// 结束行内标记（连同其后打开的标记）
+    fn close(&mut self, inline: Inline) {
+        if let Some(index) = self.open.iter().rposition(|open| *open == inline) {
+            trim_end(&mut self.content, SEPARATORS);
+            for open in self.open.split_off(index).iter().rev() {
+                self.content.push_str(open.close());
+            }
+        }
+    }
+
+    fn close_link(&mut self) {
+        if let Some(index) = self
+            .open
+            .iter()
+            .rposition(|open| matches!(open, Inline::LinkText | Inline::LinkUrl))
+        {
+            let link = self.open[index];
+            trim_end(&mut self.content, TRAILING);
+            for open in self.open.split_off(index).iter().rev() {
+                if *open != link {
+                    self.content.push_str(open.close());
+                }
+            }
+            self.content.push_str(link.close());
+        }
+    }
+
+    /// 结束当前块并开始新块
+    fn finish_block(&mut self, next: Block) {
+        self.close_link();
+        trim_end(&mut self.content, SEPARATORS);
+        for open in std::mem::take(&mut self.open).iter().rev() {
+            self.content.push_str(open.close());
+        }
+
+        let mut content = std::mem::take(&mut self.content).trim().to_string();
+        if matches!(self.block, Block::Heading(_) | Block::Bullet | Block::Numbered) {
+            trim_end(&mut content, TRAILING);
+        }
+        if !content.is_empty() {
+            self.blocks.push((self.block, content));
+        }
+        self.block = next;
+        self.trim_next = true;
+    }
+
+    fn finish(mut self) -> String {
+        self.finish_block(Block::Paragraph);
+        let mut result = String::new();
+        let mut prev: Option<Block> = None;
+        for (block, content) in &self.blocks {
+            if let Some(prev) = prev {
+                result.push_str(separator(prev, *block));
+            }
+            match block {
+                Block::Paragraph => result.push_str(content),
+                Block::Heading(level) => {
+                    result.push_str(&"#".repeat(*level));
+                    result.push(' ');
+                    result.push_str(content);
+                }
+                Block::Bullet => {
+                    result.push_str("- ");
+                    result.push_str(content);
+                }
+                Block::Numbered => {
+                    result.push_str("1. ");
+                    result.push_str(content);
+                }
+                Block::Code => {
+                    result.push_str("```\n");
+                    result.push_str(content);
+                    result.push_str("\n```");
+                }
+            }
+            prev = Some(*block);
+        }
+        result
+    }
+}
+
+/// 相邻块之间的分隔：同类列表项之间换行，其余空一行
+fn separator(prev: Block, next: Block) -> &'static str {
+    if prev == next && matches!(next, Block::Bullet | Block::Numbered) {
+        "\n"
+    } else {
+        "\n\n"
+    }
+}
+
+/// 第一个句末标点的位置（标点起止字节位置）
+fn sentence_end(text: &str) -> Option<(usize, usize)> {
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let end = match c {
+            '。' | '！' | '？' | '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+        if end {
+            return Some((i, i + c.len_utf8()));
+        }
+    }
+    None
+}
+
+fn trim_end(text: &mut String, chars: &[char]) {
+    let len = text.trim_end_matches(chars).len();
+    text.truncate(len);
+}
+
+/// 把一次听写的文字转换为 Markdown
+///
+/// 支持标题（"一级标题"～"六级标题"）、列表项、编号项、代码块（"代码块"…"结束代码块"）、
+/// 加粗 / 斜体 / 行内代码（"加粗"…"结束加粗"）和链接（"链接 文字 网址 地址"）。
+pub fn render(text: &str) -> String {
+    let mut renderer = Renderer::new();
+    for token in tokenize(text) {
+        renderer.feed(token);
+    }
+    renderer.finish()
+}
+
+/// 多次听写累积而成的 Markdown 文档（保存在文件中，跨进程保留）
+pub struct MarkdownDocument {
+    path: PathBuf,
+    text: String,
+    /// 本次说了 "新文档"，保存前把原来的文件备份为 `*.bak`
+    cleared: bool,
+}
+
+impl MarkdownDocument {
+    /// 打开文档（文件不存在时为空文档）
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text.trim_end().to_string(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            text,
+            cleared: false,
+        })
+    }
+
+    /// 完整文档
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// 追加一次听写，返回新增的内容（含与前文之间的分隔）
+    ///
+    /// 以 "新文档" 开头（后面是标点或结尾）时先清空文档，原来的文件在保存时备份。
+    pub fn append(&mut self, text: &str) -> String {
+        let text = text.trim();
+        let rest = NEW_DOCUMENT.iter().find_map(|phrase| {
+            let head = text.get(..phrase.len())?;
+            let rest = &text[phrase.len()..];
+            // "New documentation is ready" 不是命令
+            let boundary = !rest.starts_with(char::is_alphanumeric);
+            (head.eq_ignore_ascii_case(phrase) && boundary).then_some(rest)
+        });
+        let text = match rest {
+            Some(rest) => {
+                self.cleared |= !self.text.is_empty();
+                self.text.clear();
+                rest.trim_start_matches(TRAILING)
+            }
+            None => text,
+        };
+
+        let markdown = render(text);
+        if markdown.is_empty() {
+            return String::new();
+        }
+        let added = if self.text.is_empty() {
+            markdown
+        } else {
+            let prev = self.text.lines().last().map(line_block).unwrap_or(Block::Paragraph);
+            let next = markdown.lines().next().map(line_block).unwrap_or(Block::Paragraph);
+            format!("{}{}", separator(prev, next), markdown)
+        };
+        self.text.push_str(&added);
+        added
+    }
+
+    /// 保存到文件
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if self.cleared && self.path.exists() {
+            std::fs::copy(&self.path, self.backup_path())?;
+        }
+        std::fs::write(&self.path, format!("{}\n", self.text))?;
+        Ok(())
+    }
+
+    /// 说 "新文档" 之前的文档备份
+    pub fn backup_path(&self) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(".bak");
+        self.path.with_file_name(name)
+    }
+}
+
+/// 根据行首判断已有文档中的块类型（只区分列表项）
+fn line_block(line: &str) -> Block {
+    if line.starts_with("- ") {
+        Block::Bullet
+    } else if line.starts_with("1. ") {
+        Block::Numbered
+    } else {
+        Block::Paragraph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headings_and_lists() {
+        assert_eq!(
+            render("一级标题，项目简介。这是一个语音输入工具。"),
+            "# 项目简介\n\n这是一个语音输入工具。"
+        );
+        assert_eq!(
+            render("二级标题安装步骤列表项安装依赖，列表项编译。"),
+            "## 安装步骤\n\n- 安装依赖\n- 编译"
+        );
+        assert_eq!(render("编号项 first 编号项 second"), "1. first\n1. second");
+        assert_eq!(render("heading two Usage bullet build"), "## Usage\n\n- build");
+        assert_eq!(render("列表项安装依赖。编译很快。"), "- 安装依赖\n\n编译很快。");
+    }
+
+    #[test]
+    fn test_inline() {
+        assert_eq!(render("这一步，加粗，非常重要，结束加粗，不要跳过。"), "这一步，**非常重要**，不要跳过。");
+        assert_eq!(render("运行行内代码cargo build结束行内代码即可"), "运行`cargo build`即可");
+        assert_eq!(render("this is bold important end bold text"), "this is **important** text");
+        // 没有说结束时在块末尾自动补全
+        assert_eq!(render("列表项加粗注意"), "- **注意**");
+    }
+
+    #[test]
+    fn test_link() {
+        assert_eq!(
+            render("详见链接项目主页网址 github.com/example。"),
+            "详见[项目主页](github.com/example)"
+        );
+        assert_eq!(render("链接项目主页结束链接"), "[项目主页]()");
+    }
+
+    #[test]
+    fn test_code_block() {
+        assert_eq!(
+            render("示例如下代码块cargo run --release结束代码块然后打开浏览器"),
+            "示例如下\n\n```\ncargo run --release\n```\n\n然后打开浏览器"
+        );
+        // 代码块中的命令保持原文
+        assert_eq!(render("代码块 echo 加粗"), "```\necho 加粗\n```");
+    }
+
+    #[test]
+    fn test_plain_text_unchanged() {
+        assert_eq!(render("今天天气很好。"), "今天天气很好。");
+        assert_eq!(render("the boldness of it"), "the boldness of it");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(render("点击下面的字面链接"), "点击下面的链接");
+        assert_eq!(render("打开字面 网址，再登录"), "打开网址，再登录");
+        assert_eq!(render("make it literal bold please"), "make it bold please");
+        assert_eq!(render("a literal bullet point"), "a bullet point");
+    }
+
+    #[test]
+    fn test_document_accumulates() {
+        let path = std::env::temp_dir().join(format!("voice-input-doc-{}.md", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let mut document = MarkdownDocument::open(path.clone()).unwrap();
+        assert_eq!(document.append("一级标题 待办"), "# 待办");
+        assert_eq!(document.append("列表项 写文档"), "\n\n- 写文档");
+        assert_eq!(document.append("列表项 发布"), "\n- 发布");
+        document.save().unwrap();
+
+        let mut document = MarkdownDocument::open(path.clone()).unwrap();
+        assert_eq!(document.text(), "# 待办\n\n- 写文档\n- 发布");
+        assert_eq!(document.append("New documentation is ready"), "\n\nNew documentation is ready");
+        assert_eq!(document.append("新文档，一级标题 周报"), "# 周报");
+        assert_eq!(document.text(), "# 周报");
+        document.save().unwrap();
+
+        // 清空前的文档保留在备份中
+        let backup = std::fs::read_to_string(document.backup_path()).unwrap();
+        assert_eq!(backup, "# 待办\n\n- 写文档\n- 发布\n");
+
+        std::fs::remove_file(document.backup_path()).ok();
+        std::fs::remove_file(&path).ok();
+    }
+}
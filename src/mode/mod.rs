@@ -0,0 +1,24 @@
+pub mod code;
+pub mod command;
+pub mod edit;
+pub mod markdown;
+
+pub use edit::{Edit, Revisions};
+pub use markdown::MarkdownDocument;
+
+use serde::{Deserialize, Serialize};
+
+/// 听写模式：决定识别结果如何解释和输出
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// 普通听写
+    #[default]
+    Dictation,
+    /// Markdown 文档：口述结构命令，多次听写累积成一篇文档
+    Markdown,
+    /// 代码：口述命名风格（驼峰、下划线）和符号，偏向英文识别
+    Code,
+    /// 命令：把说法映射到 `[[commands]]` 中配置的 shell 命令或 D-Bus 调用
+    Command,
+}
@@ -0,0 +1,15 @@
+/// 识别过程中产生的事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecognitionEvent {
+    /// 已连接识别服务并开始录音
+    Recording,
+    /// 当前音量（0-100）
+    Level(u8),
+    /// 识别结果更新
+    Partial {
+        /// 当前完整文本（最后一段仍可能被动态修正）
+        text: String,
+        /// 已稳定的文本
+        stable: String,
+    },
+}
@@ -1,3 +1,5 @@
+pub mod event;
 pub mod xfyun_realtime;
 
-pub use xfyun_realtime::XfyunRealtimeRecognizer;
+pub use event::RecognitionEvent;
+pub use xfyun_realtime::{XfyunRealtimeRecognizer, MAX_SILENCE_DURATION};
@@ -6,20 +6,41 @@ use sha2::Sha256;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::Mutex;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use urlencoding::encode;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Sample, SampleFormat, StreamConfig};
 
+use super::RecognitionEvent;
+use crate::audio::SilenceDetector;
+use crate::transcript::{Segment, Transcript, Word};
 use crate::utils::{Result, VoiceInputError};
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// 讯飞云后端点检测（`vad_eos`）允许的最长静音时间（秒）
+pub const MAX_SILENCE_DURATION: f32 = 10.0;
+
 /// 讯飞云实时语音识别器
 pub struct XfyunRealtimeRecognizer {
     app_id: String,
     api_secret: String,
     api_key: String,
+    /// 识别事件推送通道
+    event_tx: Option<UnboundedSender<RecognitionEvent>>,
+    /// 外部控制的运行标志（置为 false 即停止录音）
+    running: Option<Arc<AtomicBool>>,
+    /// 识别语言（zh_cn | en_us）
+    language: String,
+    /// 方言（mandarin | cantonese 等，仅中文有效）
+    accent: String,
+    /// 每个词的候选数（讯飞云 wbest，1-5）
+    candidates: u8,
+    /// 是否自动添加标点（讯飞云 ptt）
+    punctuation: bool,
+    /// 结束词（出现在识别结果末尾时立即发送结束帧）
+    stop_keywords: Vec<String>,
 }
 
 impl XfyunRealtimeRecognizer {
@@ -29,9 +50,67 @@ impl XfyunRealtimeRecognizer {
             app_id,
             api_secret,
             api_key,
+            event_tx: None,
+            running: None,
+            language: "zh_cn".to_string(),
+            accent: "mandarin".to_string(),
+            candidates: 1,
+            punctuation: true,
+            stop_keywords: Vec::new(),
         }
     }
 
+    /// 设置识别语言和方言
+    pub fn with_language(mut self, language: &str, accent: &str) -> Self {
+        self.language = language.to_string();
+        self.accent = accent.to_string();
+        self
+    }
+
+    /// 设置每个词返回的候选数（1-5）
+    pub fn with_candidates(mut self, candidates: u8) -> Self {
+        self.candidates = candidates.clamp(1, 5);
+        self
+    }
+
+    /// 设置是否自动添加标点
+    pub fn with_punctuation(mut self, punctuation: bool) -> Self {
+        self.punctuation = punctuation;
+        self
+    }
+
+    /// 设置结束词
+    ///
+    /// 识别结果以结束词结尾时不再等待静音超时，立即结束录音，
+    /// 最终结果中去掉结束词。
+    pub fn with_stop_keywords(mut self, keywords: &[String]) -> Self {
+        self.stop_keywords = keywords
+            .iter()
+            .map(|keyword| keyword.trim().to_string())
+            .filter(|keyword| !keyword.is_empty())
+            .collect();
+        self
+    }
+
+    /// 设置识别事件推送通道
+    ///
+    /// 开始录音时发送 [`RecognitionEvent::Recording`]，录音过程中定期发送
+    /// [`RecognitionEvent::Level`]，每次识别文本变化时发送 [`RecognitionEvent::Partial`]。
+    /// 动态修正可能改写之前已发送过的内容，接收方需要自行比较差异。
+    pub fn with_event_sender(mut self, tx: UnboundedSender<RecognitionEvent>) -> Self {
+        self.event_tx = Some(tx);
+        self
+    }
+
+    /// 使用外部运行标志控制录音
+    ///
+    /// 设置后不再注册 Ctrl+C 处理，由调用方把标志置为 false 来结束录音
+    /// （例如守护进程收到 stop 命令时）。
+    pub fn with_running_flag(mut self, running: Arc<AtomicBool>) -> Self {
+        self.running = Some(running);
+        self
+    }
+
     /// 生成鉴权 URL
     fn generate_auth_url(&self) -> Result<String> {
         let host = "iat-api.xfyun.cn";
@@ -68,12 +147,12 @@ impl XfyunRealtimeRecognizer {
         Ok(url)
     }
 
-    /// 实时流式识别（边录边发送）
+    /// 实时流式识别（边录边发送），返回带分段和词级时间的结果
     pub async fn recognize_realtime(
         &self,
         sample_rate: u32,
         silence_duration: f32,
-    ) -> Result<String> {
+    ) -> Result<Transcript> {
         println!("🌐 正在连接讯飞语音识别服务...");
 
         // 生成鉴权 URL
@@ -89,18 +168,27 @@ impl XfyunRealtimeRecognizer {
         println!("✅ 连接成功\n");
 
         let (write, mut read) = ws_stream.split();
-        let result = Arc::new(Mutex::new(String::new()));
+        let result = Arc::new(Mutex::new(ResultAssembler::default()));
         let result_clone = result.clone();
-        let is_running = Arc::new(AtomicBool::new(true));
-        let is_running_for_ctrlc = is_running.clone();
-        let is_running_for_receive = is_running.clone();
+        let event_tx = self.event_tx.clone();
+        let is_running = match &self.running {
+            Some(running) => running.clone(),
+            None => {
+                let is_running = Arc::new(AtomicBool::new(true));
+                let is_running_for_ctrlc = is_running.clone();
+
+                // Ctrl+C 处理
+                ctrlc::set_handler(move || {
+                    println!("\n\n⏹️  用户停止录音...");
+                    is_running_for_ctrlc.store(false, Ordering::SeqCst);
+                })
+                .ok();
 
-        // Ctrl+C 处理
-        ctrlc::set_handler(move || {
-            println!("\n\n⏹️  用户停止录音...");
-            is_running_for_ctrlc.store(false, Ordering::SeqCst);
-        })
-        .ok();
+                is_running
+            }
+        };
+        let is_running_for_receive = is_running.clone();
+        let stop_keywords = self.stop_keywords.clone();
 
         // 启动接收任务
         let receive_task = tokio::spawn(async move {
@@ -124,29 +212,27 @@ impl XfyunRealtimeRecognizer {
                             // 提取识别结果
                             if let Some(data) = response.get("data") {
                                 if let Some(result_obj) = data.get("result") {
-                                    if let Some(ws) = result_obj.get("ws") {
-                                        if let Some(ws_array) = ws.as_array() {
-                                            let mut text_result = String::new();
-                                            for item in ws_array {
-                                                if let Some(cw) = item.get("cw") {
-                                                    if let Some(cw_array) = cw.as_array() {
-                                                        for word in cw_array {
-                                                            if let Some(w) = word.get("w") {
-                                                                if let Some(w_str) = w.as_str() {
-                                                                    text_result.push_str(w_str);
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            if !text_result.is_empty() {
-                                                let mut result_lock = result_clone.lock().await;
-                                                result_lock.push_str(&text_result);
-                                                // 实时显示
-                                                print!("\r识别中: {}", result_lock);
-                                                use std::io::Write;
-                                                std::io::stdout().flush().ok();
+                                    let mut assembler = result_clone.lock().await;
+                                    if assembler.apply(result_obj) {
+                                        // 实时显示
+                                        print!("\r识别中: {}", assembler.text());
+                                        use std::io::Write;
+                                        std::io::stdout().flush().ok();
+
+                                        // 推送识别结果更新
+                                        if let Some(tx) = &event_tx {
+                                            tx.send(RecognitionEvent::Partial {
+                                                text: assembler.text(),
+                                                stable: assembler.stable_text(),
+                                            })
+                                            .ok();
+                                        }
+
+                                        // 说出结束词：停止录音，发送端随即发送结束帧
+                                        if is_running_for_receive.load(Ordering::SeqCst) {
+                                            if let Some((_, keyword)) = find_stop_keyword(&assembler.text(), &stop_keywords) {
+                                                println!("\n🛑 检测到结束词 \"{}\"，停止录音", keyword);
+                                                is_running_for_receive.store(false, Ordering::SeqCst);
                                             }
                                         }
                                     }
@@ -178,6 +264,9 @@ impl XfyunRealtimeRecognizer {
             }
         });
 
+        // 会话被中止（future 被丢弃）时一并结束接收任务，关闭 WebSocket
+        let _receive_guard = AbortOnDrop(receive_task.abort_handle());
+
         // 录音并发送
         let app_id = self.app_id.clone();
         let send_result = self.record_and_send(
@@ -188,20 +277,26 @@ impl XfyunRealtimeRecognizer {
             silence_duration,
         ).await;
 
-        // 等待接收完成
-        receive_task.await.map_err(|e| {
-            VoiceInputError::Recognition(format!("接收任务失败: {}", e))
-        })?;
+        // 等待接收完成（没有发出音频或发送失败时服务端不会再返回结果，不再等待）
+        if matches!(send_result, Ok(audio_ms) if audio_ms > 0) {
+            receive_task.await.map_err(|e| {
+                VoiceInputError::Recognition(format!("接收任务失败: {}", e))
+            })?;
+        } else {
+            receive_task.abort();
+        }
 
         // 检查发送是否出错
-        send_result?;
+        let audio_ms = send_result?;
 
-        let final_result = result.lock().await.clone();
+        let mut assembler = result.lock().await;
+        assembler.strip_stop_keyword(&self.stop_keywords);
+        let transcript = assembler.transcript(audio_ms);
         println!("\n");  // 换行
-        Ok(final_result)
+        Ok(transcript)
     }
 
-    /// 录音并实时发送
+    /// 录音并实时发送，返回已发送音频的时长（毫秒）
     async fn record_and_send(
         &self,
         mut write: futures::stream::SplitSink<
@@ -214,7 +309,7 @@ impl XfyunRealtimeRecognizer {
         app_id: String,
         is_running: Arc<AtomicBool>,
         silence_duration: f32,
-    ) -> Result<()> {
+    ) -> Result<u64> {
         // 获取音频设备
         let host = cpal::default_host();
         let device = host
@@ -328,13 +423,18 @@ impl XfyunRealtimeRecognizer {
             VoiceInputError::AudioRecord(format!("启动音频流失败: {}", e))
         })?;
 
+        if let Some(tx) = &self.event_tx {
+            tx.send(RecognitionEvent::Recording).ok();
+        }
+
         println!("🎤 开始录音... (按 Ctrl+C 停止)");
         println!("💡 说完话后保持静音 {:.1} 秒即可自动停止\n", silence_duration);
 
         let mut status = 0; // 0: 首帧, 1: 中间帧, 2: 末帧
+        let mut frame_count: u64 = 0;
 
         // 将静音持续时间转换为毫秒（讯飞云 vad_eos 参数）
-        let vad_eos = (silence_duration * 1000.0) as u32;
+        let vad_eos = (silence_duration.min(MAX_SILENCE_DURATION) * 1000.0) as u32;
 
         // 发送音频帧
         while is_running.load(Ordering::SeqCst) {
@@ -351,6 +451,17 @@ impl XfyunRealtimeRecognizer {
             };
 
             // 转换为 16-bit PCM
+            // 每 4 帧（160ms）推送一次音量
+            frame_count += 1;
+            if frame_count.is_multiple_of(4) {
+                if let Some(tx) = &self.event_tx {
+                    tx.send(RecognitionEvent::Level(
+                        SilenceDetector::get_volume_percentage(&audio_data),
+                    ))
+                    .ok();
+                }
+            }
+
             let pcm_data: Vec<i16> = audio_data.iter().map(|&s| (s * 32767.0) as i16).collect();
             let bytes: Vec<u8> = pcm_data
                 .iter()
@@ -362,15 +473,17 @@ impl XfyunRealtimeRecognizer {
             // 构建消息
             let frame_msg = if status == 0 {
                 // 首帧
-                serde_json::json!({
+                let mut first = serde_json::json!({
                     "common": {
                         "app_id": app_id
                     },
                     "business": {
-                        "language": "zh_cn",
+                        "language": self.language,
                         "domain": "iat",
-                        "accent": "mandarin",
-                        "vad_eos": vad_eos  // 使用配置的静音超时时间
+                        "accent": self.accent,
+                        "vad_eos": vad_eos,  // 使用配置的静音超时时间
+                        "dwa": "wpgs",       // 开启动态修正
+                        "vinfo": 1           // 返回词级时间 wb/we
                     },
                     "data": {
                         "status": 0,
@@ -378,7 +491,16 @@ impl XfyunRealtimeRecognizer {
                         "encoding": "raw",
                         "audio": audio_b64
                     }
-                })
+                });
+                // 多候选（结果中每个 cw 数组包含多个候选词）
+                if self.candidates > 1 {
+                    first["business"]["wbest"] = serde_json::json!(self.candidates);
+                }
+                // 关闭自动标点
+                if !self.punctuation {
+                    first["business"]["ptt"] = serde_json::json!(0);
+                }
+                first
             } else {
                 // 中间帧
                 serde_json::json!({
@@ -402,6 +524,13 @@ impl XfyunRealtimeRecognizer {
             }
         }
 
+        // 一帧音频都没有发送时不能只发结束帧（服务端会报错），直接断开
+        if status == 0 {
+            drop(stream);
+            write.close().await.ok();
+            return Ok(0);
+        }
+
         // 发送结束帧
         let end_frame = serde_json::json!({
             "data": {
@@ -418,6 +547,431 @@ impl XfyunRealtimeRecognizer {
         // 停止录音
         drop(stream);
 
-        Ok(())
+        Ok(frame_count * 40)
+    }
+}
+
+/// 丢弃时中止对应的任务（任务已结束时无影响）
+struct AbortOnDrop(tokio::task::AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// 识别结果拼装器
+///
+/// 开启动态修正（`dwa=wpgs`）后，讯飞云每条结果带有序号 `sn`，
+/// `pgs=apd` 表示追加，`pgs=rpl` 表示替换 `rg` 范围内的旧结果。
+/// 每条结果的 `bg`/`ed` 是该分段相对录音开始的起止时间（毫秒）；
+/// 开启 `vinfo` 后每个词带有 `wb`/`we`（相对分段开始的帧数，1 帧 = 10ms）
+/// 和置信度 `sc`。
+#[derive(Debug, Default)]
+pub(crate) struct ResultAssembler {
+    /// 按 `sn` 排列的分段结果（被替换的分段为 None）
+    segments: Vec<Option<Segment>>,
+}
+
+/// 结束词前后可以忽略的标点和空白
+const TRAILING: &[char] = &[' ', '。', '，', '、', '！', '？', '.', ',', '!', '?'];
+
+/// 文本（忽略末尾标点）以结束词结尾时，返回结束词的起始位置和结束词
+///
+/// 英文结束词忽略大小写，并且前面不能紧接字母（"Rover" 不匹配 "over"）。
+fn find_stop_keyword<'a>(text: &str, keywords: &'a [String]) -> Option<(usize, &'a str)> {
+    let body = text.trim_end_matches(TRAILING);
+    keywords.iter().find_map(|keyword| {
+        let start = body.len().checked_sub(keyword.len())?;
+        if !body.is_char_boundary(start) || !body[start..].eq_ignore_ascii_case(keyword) {
+            return None;
+        }
+        let joined = keyword.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && body[..start].ends_with(|c: char| c.is_ascii_alphanumeric());
+        (!joined).then_some((start, keyword.as_str()))
+    })
+}
+
+/// 截断分段文本，并同步截断词列表（词按顺序拼接成分段文本）
+fn truncate_segment(segment: &mut Segment, cut: usize) {
+    segment.text.truncate(cut);
+    let mut offset = 0;
+    segment.words.retain_mut(|word| {
+        let begin = offset;
+        offset += word.text.len();
+        if begin >= cut {
+            return false;
+        }
+        if offset > cut {
+            word.text.truncate(cut - begin);
+        }
+        true
+    });
+}
+
+/// 讯飞云时间单位：1 帧 = 10ms
+const FRAME_MS: u64 = 10;
+
+impl ResultAssembler {
+    /// 应用一条识别结果，返回文本是否有变化
+    pub(crate) fn apply(&mut self, result: &serde_json::Value) -> bool {
+        let start_ms = result.get("bg").and_then(|v| v.as_u64()).unwrap_or(0);
+        let words = Self::extract_words(result, start_ms);
+        let segment = Segment {
+            start_ms,
+            end_ms: result.get("ed").and_then(|v| v.as_u64()).unwrap_or(0),
+            text: words.iter().map(|w| w.text.as_str()).collect(),
+            words,
+        };
+        let sn = result
+            .get("sn")
+            .and_then(|v| v.as_u64())
+            .map(|sn| sn as usize)
+            .unwrap_or(self.segments.len() + 1)
+            .max(1);
+
+        let before = self.text();
+
+        // 替换模式：先清除被修正的分段
+        if result.get("pgs").and_then(|v| v.as_str()) == Some("rpl") {
+            if let Some(rg) = result.get("rg").and_then(|v| v.as_array()) {
+                let start = rg.first().and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+                let end = rg.get(1).and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+                for index in start.max(1)..=end {
+                    if let Some(segment) = self.segments.get_mut(index - 1) {
+                        *segment = None;
+                    }
+                }
+            }
+        }
+
+        if self.segments.len() < sn {
+            self.segments.resize(sn, None);
+        }
+        self.segments[sn - 1] = Some(segment);
+
+        self.text() != before
+    }
+
+    /// 当前完整文本
+    pub(crate) fn text(&self) -> String {
+        self.segments.iter().flatten().map(|s| s.text.as_str()).collect()
+    }
+
+    /// 已稳定的文本（最后一个分段仍可能被修正，不计入）
+    pub(crate) fn stable_text(&self) -> String {
+        let present: Vec<&str> = self.segments.iter().flatten().map(|s| s.text.as_str()).collect();
+        match present.split_last() {
+            Some((_, stable)) => stable.concat(),
+            None => String::new(),
+        }
+    }
+
+    /// 去掉末尾的结束词（连同前后的标点），返回是否去掉了
+    ///
+    /// 结束词可能被拆到两个分段中（"完" | "毕"），后面也可能还有只含标点的分段，
+    /// 所以在拼接后的全文中查找，再按位置截断各个分段。
+    pub(crate) fn strip_stop_keyword(&mut self, keywords: &[String]) -> bool {
+        let text = self.text();
+        let Some((start, _)) = find_stop_keyword(&text, keywords) else {
+            return false;
+        };
+
+        // 结束词前面的标点只在同一分段内去掉，保留上一句的句号
+        let mut offset = 0;
+        let mut cut = start;
+        for segment in self.segments.iter().flatten() {
+            let end = offset + segment.text.len();
+            if (offset..end).contains(&start) {
+                cut = offset + text[offset..start].trim_end_matches(TRAILING).len();
+                break;
+            }
+            offset = end;
+        }
+
+        let mut offset = 0;
+        for segment in self.segments.iter_mut().flatten() {
+            let begin = offset;
+            offset += segment.text.len();
+            if offset > cut {
+                truncate_segment(segment, cut.saturating_sub(begin));
+            }
+        }
+        true
+    }
+
+    /// 最终结果（去掉空分段）
+    ///
+    /// 中间结果的 `ed` 可能为 0，此时用下一分段的开始时间补齐，
+    /// 最后一个分段用录音总时长 `audio_ms` 补齐；缺少结束时间的词同理。
+    pub(crate) fn transcript(&self, audio_ms: u64) -> Transcript {
+        let mut segments: Vec<Segment> = self
+            .segments
+            .iter()
+            .flatten()
+            .filter(|s| !s.text.is_empty())
+            .cloned()
+            .collect();
+
+        for index in 0..segments.len() {
+            if segments[index].end_ms <= segments[index].start_ms {
+                let next_start = segments.get(index + 1).map(|s| s.start_ms);
+                let end = next_start
+                    .filter(|&start| start > segments[index].start_ms)
+                    .unwrap_or(audio_ms);
+                segments[index].end_ms = end.max(segments[index].start_ms);
+            }
+
+            let segment_end = segments[index].end_ms;
+            let words = &mut segments[index].words;
+            for i in 0..words.len() {
+                if words[i].end_ms <= words[i].start_ms {
+                    let next_start = words.get(i + 1).map(|w| w.start_ms);
+                    let end = next_start
+                        .filter(|&start| start > words[i].start_ms)
+                        .unwrap_or(segment_end);
+                    words[i].end_ms = end.max(words[i].start_ms);
+                }
+            }
+        }
+
+        Transcript {
+            segments,
+            duration_ms: audio_ms,
+            processed: None,
+        }
+    }
+
+    /// 提取一条结果中的词
+    fn extract_words(result: &serde_json::Value, segment_start_ms: u64) -> Vec<Word> {
+        let Some(ws_array) = result.get("ws").and_then(|ws| ws.as_array()) else {
+            return Vec::new();
+        };
+
+        ws_array
+            .iter()
+            .filter_map(|item| {
+                // cw 是同一位置的候选列表（n-best），只取最可能的一个
+                let cw_array = item.get("cw").and_then(|cw| cw.as_array())?;
+                let mut candidates: Vec<&serde_json::Value> = cw_array
+                    .iter()
+                    .filter(|word| word.get("w").and_then(|w| w.as_str()).is_some())
+                    .collect();
+                // 按分数稳定排序：没有分数时保持服务端给出的顺序
+                candidates.sort_by(|a, b| {
+                    let score = |v: &serde_json::Value| v.get("sc").and_then(|s| s.as_f64()).unwrap_or(0.0);
+                    score(b).total_cmp(&score(a))
+                });
+                let (first, rest) = candidates.split_first()?;
+                let text = first.get("w").and_then(|w| w.as_str())?.to_string();
+                let alternatives: Vec<String> = rest
+                    .iter()
+                    .filter_map(|word| word.get("w").and_then(|w| w.as_str()))
+                    .filter(|w| *w != text)
+                    .map(str::to_string)
+                    .collect();
+
+                // 优先使用词级 wb/we，否则退回到 ws 的 bg；都是相对本句开始的帧数
+                let frame = |key: &str| first.get(key).and_then(|v| v.as_u64());
+                let (start_ms, end_ms) = match (frame("wb"), frame("we")) {
+                    (Some(wb), Some(we)) => (
+                        segment_start_ms + wb * FRAME_MS,
+                        segment_start_ms + we * FRAME_MS,
+                    ),
+                    _ => {
+                        let bg = item.get("bg").and_then(|v| v.as_u64()).unwrap_or(0);
+                        let start = segment_start_ms + bg * FRAME_MS;
+                        (start, start)
+                    }
+                };
+
+                Some(Word {
+                    text,
+                    start_ms,
+                    end_ms,
+                    score: first.get("sc").and_then(|v| v.as_f64()),
+                    alternatives,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn result(sn: u64, pgs: &str, rg: Option<[u64; 2]>, words: &[&str]) -> serde_json::Value {
+        let ws: Vec<_> = words.iter().map(|w| json!({ "cw": [{ "w": w }] })).collect();
+        let mut value = json!({ "sn": sn, "pgs": pgs, "ws": ws });
+        if let Some(rg) = rg {
+            value["rg"] = json!(rg);
+        }
+        value
+    }
+
+    #[test]
+    fn test_append_segments() {
+        let mut assembler = ResultAssembler::default();
+        assert!(assembler.apply(&result(1, "apd", None, &["今天", "天气"])));
+        assert!(assembler.apply(&result(2, "apd", None, &["很好"])));
+        assert_eq!(assembler.text(), "今天天气很好");
+        assert_eq!(assembler.stable_text(), "今天天气");
+    }
+
+    #[test]
+    fn test_replace_segments() {
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&result(1, "apd", None, &["今天"]));
+        assembler.apply(&result(2, "apd", None, &["天7"]));
+        assembler.apply(&result(3, "rpl", Some([2, 2]), &["天气很好"]));
+        assert_eq!(assembler.text(), "今天天气很好");
+
+        assembler.apply(&result(4, "rpl", Some([1, 3]), &["今天天气真好"]));
+        assert_eq!(assembler.text(), "今天天气真好");
+        assert_eq!(assembler.stable_text(), "");
+    }
+
+    #[test]
+    fn test_result_without_sn() {
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&json!({ "ws": [{ "cw": [{ "w": "你好" }] }] }));
+        assembler.apply(&json!({ "ws": [{ "cw": [{ "w": "世界" }] }] }));
+        assert_eq!(assembler.text(), "你好世界");
+    }
+
+    #[test]
+    fn test_segment_times() {
+        let mut assembler = ResultAssembler::default();
+        let mut first = result(1, "apd", None, &["今天天气很好"]);
+        first["bg"] = json!(120);
+        first["ed"] = json!(1800);
+        let mut second = result(2, "apd", None, &["出去走走"]);
+        second["bg"] = json!(2500);
+        let mut third = result(3, "apd", None, &["吧"]);
+        third["bg"] = json!(3600);
+        assembler.apply(&first);
+        assembler.apply(&second);
+        assembler.apply(&third);
+
+        let transcript = assembler.transcript(4200);
+        let segments = &transcript.segments;
+        assert_eq!(segments.len(), 3);
+        assert_eq!((segments[0].start_ms, segments[0].end_ms), (120, 1800));
+        // 缺少 ed 时用下一分段开始时间和录音时长补齐
+        assert_eq!((segments[1].start_ms, segments[1].end_ms), (2500, 3600));
+        assert_eq!((segments[2].start_ms, segments[2].end_ms), (3600, 4200));
+        assert_eq!(transcript.text(), "今天天气很好出去走走吧");
+        assert_eq!(transcript.duration_ms, 4200);
+    }
+
+    #[test]
+    fn test_word_times_and_scores() {
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&json!({
+            "sn": 1,
+            "bg": 500,
+            "ed": 2000,
+            "ws": [
+                { "bg": 1, "cw": [{ "w": "今天", "wb": 1, "we": 40, "sc": 0.92 }] },
+                { "bg": 41, "cw": [{ "w": "下雨", "wb": 41, "we": 120, "sc": 0.31 }] },
+                { "bg": 120, "cw": [{ "w": "。" }] }
+            ]
+        }));
+
+        let transcript = assembler.transcript(2500);
+        let words: Vec<&Word> = transcript.words().collect();
+        assert_eq!(words.len(), 3);
+        assert_eq!((words[0].start_ms, words[0].end_ms), (510, 900));
+        assert_eq!(words[0].score, Some(0.92));
+        assert_eq!((words[1].start_ms, words[1].end_ms), (910, 1700));
+        // 标点没有 wb/we，使用 ws.bg 并以分段结束时间补齐
+        assert_eq!((words[2].start_ms, words[2].end_ms), (1700, 2000));
+        assert_eq!(words[2].score, None);
+        assert_eq!(transcript.text(), "今天下雨。");
+    }
+
+    #[test]
+    fn test_word_time_bases_agree() {
+        // 同一个词有没有 wb/we，起始时间都相对本句开始计算
+        let word = |cw: serde_json::Value| {
+            let mut assembler = ResultAssembler::default();
+            assembler.apply(&json!({ "sn": 1, "bg": 3000, "ed": 4000, "ws": [{ "bg": 25, "cw": [cw] }] }));
+            assembler.transcript(4000).words().next().unwrap().start_ms
+        };
+        assert_eq!(word(json!({ "w": "开会", "wb": 25, "we": 60 })), 3250);
+        assert_eq!(word(json!({ "w": "开会" })), 3250);
+    }
+
+    #[test]
+    fn test_candidates_pick_best() {
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&json!({
+            "sn": 1,
+            "ws": [
+                { "cw": [{ "w": "今天" }] },
+                { "cw": [{ "w": "期中", "sc": 0.4 }, { "w": "其中", "sc": 0.7 }, { "w": "期终", "sc": 0.1 }] },
+                { "cw": [{ "w": "开会" }, { "w": "开会" }] }
+            ]
+        }));
+
+        assert_eq!(assembler.text(), "今天其中开会");
+        let transcript = assembler.transcript(1000);
+        let words: Vec<&Word> = transcript.words().collect();
+        assert_eq!(words[1].alternatives, ["期中", "期终"]);
+        assert_eq!(words[1].score, Some(0.7));
+        // 重复的候选不算作备选
+        assert!(words[2].alternatives.is_empty());
+    }
+
+    #[test]
+    fn test_find_stop_keyword() {
+        let keywords = vec!["完毕".to_string(), "over".to_string()];
+        assert_eq!(find_stop_keyword("今天就到这里完毕。", &keywords), Some((18, "完毕")));
+        assert_eq!(find_stop_keyword("That's all, Over.", &keywords), Some((12, "over")));
+        assert_eq!(find_stop_keyword("完毕之后再说", &keywords), None);
+        assert_eq!(find_stop_keyword("Land the rover", &keywords), None);
+        assert_eq!(find_stop_keyword("任何内容", &[]), None);
+    }
+
+    #[test]
+    fn test_strip_stop_keyword() {
+        let keywords = vec!["完毕".to_string()];
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&result(1, "apd", None, &["明天", "开会", "，", "完", "毕", "。"]));
+        assert!(assembler.strip_stop_keyword(&keywords));
+        let transcript = assembler.transcript(1000);
+        assert_eq!(transcript.text(), "明天开会");
+        let words: Vec<_> = transcript.segments[0].words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, ["明天", "开会"]);
+
+        // 结束词单独成为一个分段
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&result(1, "apd", None, &["你好", "。"]));
+        assembler.apply(&result(2, "apd", None, &["完毕", "。"]));
+        assert!(assembler.strip_stop_keyword(&keywords));
+        assert_eq!(assembler.transcript(1000).text(), "你好。");
+
+        assert!(!assembler.strip_stop_keyword(&keywords));
+
+        // 最后一个分段只有标点
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&result(1, "apd", None, &["明天", "开会", "，", "完毕"]));
+        assembler.apply(&result(2, "apd", None, &["。"]));
+        assert!(assembler.strip_stop_keyword(&keywords));
+        let transcript = assembler.transcript(1000);
+        assert_eq!(transcript.text(), "明天开会");
+        assert_eq!(transcript.segments.len(), 1);
+
+        // 结束词被拆到两个分段
+        let mut assembler = ResultAssembler::default();
+        assembler.apply(&result(1, "apd", None, &["明天", "开会", "，", "完"]));
+        assembler.apply(&result(2, "apd", None, &["毕", "。"]));
+        assert!(assembler.strip_stop_keyword(&keywords));
+        let transcript = assembler.transcript(1000);
+        assert_eq!(transcript.text(), "明天开会");
+        let words: Vec<_> = transcript.words().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, ["明天", "开会"]);
     }
 }
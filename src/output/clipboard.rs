@@ -83,7 +83,7 @@ mod tests {
     fn test_clipboard_basic() {
         // 这个测试可能在无显示服务器的环境（如 CI）中失败
         if std::env::var("CI").is_err() {
-            let mut clipboard = ClipboardOutput::new();
+            let clipboard = ClipboardOutput::new();
             if let Ok(mut clip) = clipboard {
                 let test_text = "测试文本";
                 assert!(clip.copy(test_text).is_ok());
@@ -0,0 +1,164 @@
+use crate::config::ConfidenceConfig;
+use crate::transcript::Segment;
+
+/// 终端高亮：黄色下划线
+const COLOR_START: &str = "\x1b[4;33m";
+const COLOR_END: &str = "\x1b[0m";
+
+/// 标记置信度低的词，方便只校对有风险的部分
+#[derive(Debug, Clone)]
+pub struct ConfidenceMarker {
+    threshold: f64,
+    marker_start: String,
+    marker_end: String,
+}
+
+impl ConfidenceMarker {
+    /// 根据配置创建（未开启时返回 None）
+    pub fn from_config(config: &ConfidenceConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            threshold: config.threshold,
+            marker_start: config.marker_start.clone(),
+            marker_end: config.marker_end.clone(),
+        })
+    }
+
+    /// 用文字标记包围低置信度的词（用于文件输出）
+    ///
+    /// `text` 是最终输出的文本（可能经过后处理），按顺序在其中查找各个词；
+    /// 被后处理改写、找不到的词不做标记。
+    pub fn mark(&self, text: &str, segments: &[Segment]) -> String {
+        self.render(text, segments, &self.marker_start, &self.marker_end)
+    }
+
+    /// 用终端颜色高亮低置信度的词
+    pub fn colorize(&self, text: &str, segments: &[Segment]) -> String {
+        self.render(text, segments, COLOR_START, COLOR_END)
+    }
+
+    /// 低置信度词的数量
+    pub fn count(&self, segments: &[Segment]) -> usize {
+        if !has_scores(segments) {
+            return 0;
+        }
+        segments
+            .iter()
+            .flat_map(|s| &s.words)
+            .filter(|w| self.is_low(w.score))
+            .count()
+    }
+
+    fn is_low(&self, score: Option<f64>) -> bool {
+        score.is_some_and(|score| score < self.threshold)
+    }
+
+    fn render(&self, text: &str, segments: &[Segment], start: &str, end: &str) -> String {
+        // 没有任何分数时（引擎未返回 sc）不标记，避免整段都被标出
+        if !has_scores(segments) {
+            return text.to_string();
+        }
+
+        let mut output = String::new();
+        let mut cursor = 0;
+        for word in segments.iter().flat_map(|s| &s.words) {
+            if word.text.is_empty() {
+                continue;
+            }
+            let Some(offset) = text[cursor..].find(&word.text) else {
+                continue;
+            };
+            let position = cursor + offset;
+            let word_end = position + word.text.len();
+
+            if self.is_low(word.score) {
+                output.push_str(&text[cursor..position]);
+                output.push_str(start);
+                output.push_str(&word.text);
+                output.push_str(end);
+            } else {
+                output.push_str(&text[cursor..word_end]);
+            }
+            cursor = word_end;
+        }
+        output.push_str(&text[cursor..]);
+        output
+    }
+}
+
+/// 是否有任意一个词带有非零分数
+fn has_scores(segments: &[Segment]) -> bool {
+    segments
+        .iter()
+        .flat_map(|s| &s.words)
+        .any(|w| w.score.is_some_and(|score| score > 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Word;
+
+    fn segment(words: &[(&str, Option<f64>)]) -> Segment {
+        let words: Vec<Word> = words
+            .iter()
+            .map(|&(text, score)| Word {
+                text: text.to_string(),
+                start_ms: 0,
+                end_ms: 0,
+                score,
+                alternatives: Vec::new(),
+            })
+            .collect();
+        Segment {
+            start_ms: 0,
+            end_ms: 0,
+            text: words.iter().map(|w| w.text.as_str()).collect(),
+            words,
+        }
+    }
+
+    fn marker() -> ConfidenceMarker {
+        ConfidenceMarker::from_config(&ConfidenceConfig {
+            enabled: true,
+            threshold: 0.6,
+            marker_start: "[[".to_string(),
+            marker_end: "]]".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mark_low_confidence() {
+        let segments = [segment(&[("明天", Some(0.95)), ("下雨", Some(0.4)), ("。", None)])];
+        let marker = marker();
+        assert_eq!(marker.mark("明天下雨。", &segments), "明天[[下雨]]。");
+        assert_eq!(marker.colorize("明天下雨。", &segments), "明天\x1b[4;33m下雨\x1b[0m。");
+        assert_eq!(marker.count(&segments), 1);
+
+        // 后处理改写过的文本：仍能找到的词照常标记
+        assert_eq!(marker.mark("明天 下雨", &segments), "明天 [[下雨]]");
+        assert_eq!(marker.mark("明天会下大雨", &segments), "明天会下大雨");
+    }
+
+    #[test]
+    fn test_without_scores() {
+        // 讯飞云未返回分数时 sc 全为 0
+        let segments = [
+            segment(&[("今天", Some(0.0)), ("很好", Some(0.0))]),
+            Segment {
+                start_ms: 0,
+                end_ms: 0,
+                text: "旧记录".to_string(),
+                words: Vec::new(),
+            },
+        ];
+        let marker = marker();
+        assert_eq!(marker.mark("今天很好旧记录", &segments), "今天很好旧记录");
+        assert_eq!(marker.count(&segments), 0);
+    }
+
+    #[test]
+    fn test_disabled() {
+        assert!(ConfidenceMarker::from_config(&ConfidenceConfig::default()).is_none());
+    }
+}
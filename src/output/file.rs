@@ -0,0 +1,75 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::utils::Result;
+
+/// 文件输出：每次识别结果追加为一行
+pub struct FileOutput {
+    path: PathBuf,
+}
+
+impl FileOutput {
+    /// 创建文件输出（支持 ~ 展开）
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: PathBuf::from(shellexpand::tilde(path).into_owned()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 追加一行文本
+    pub fn append(&self, text: &str) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", text)?;
+
+        tracing::info!("已写入 {} 个字符到 {}", text.chars().count(), self.path.display());
+        Ok(())
+    }
+
+    /// 把文件末尾的 `previous` 替换为 `text`（语音编辑的结果）
+    ///
+    /// 文件末尾不是 `previous`（例如之后手动改过文件）时改为追加。
+    pub fn replace_last(&self, previous: &str, text: &str) -> Result<()> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let Some(head) = content.strip_suffix(&format!("{}\n", previous)) else {
+            return self.append(text);
+        };
+
+        std::fs::write(&self.path, format!("{}{}\n", head, text))?;
+        tracing::info!("已替换 {} 中的上一次结果", self.path.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_last() {
+        let dir = std::env::temp_dir().join(format!("voice-input-file-{}", std::process::id()));
+        let output = FileOutput::new(dir.join("notes.txt").to_str().unwrap());
+        output.append("第一条").unwrap();
+        output.append("请张三准备材料").unwrap();
+
+        output.replace_last("请张三准备材料", "请李四准备材料").unwrap();
+        assert_eq!(std::fs::read_to_string(output.path()).unwrap(), "第一条\n请李四准备材料\n");
+        // 末尾不是上一次结果时追加
+        output.replace_last("请张三准备材料", "明天开会").unwrap();
+        assert_eq!(std::fs::read_to_string(output.path()).unwrap(), "第一条\n请李四准备材料\n明天开会\n");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,3 +1,11 @@
 pub mod clipboard;
+pub mod confidence;
+pub mod file;
+pub mod notify;
+pub mod typer;
 
 pub use clipboard::ClipboardOutput;
+pub use confidence::ConfidenceMarker;
+pub use file::FileOutput;
+pub use notify::DesktopNotifier;
+pub use typer::{StreamingTyper, TypingOutput};
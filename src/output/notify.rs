@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use zbus::zvariant::Value;
+use zbus::{Connection, MatchRule, MessageStream};
+
+use crate::utils::{Result, VoiceInputError};
+
+const APP_NAME: &str = "语音输入";
+const APP_ICON: &str = "audio-input-microphone";
+
+/// 结果预览的最大字符数
+const PREVIEW_CHARS: usize = 80;
+
+/// 桌面通知（org.freedesktop.Notifications）
+///
+/// 同一个通知器发出的通知会原地替换上一条，而不是在通知栏中堆叠。
+#[derive(Debug, Clone)]
+pub struct DesktopNotifier {
+    connection: Connection,
+    /// 上一条通知的 ID（0 表示新建）
+    id: Arc<AtomicU32>,
+}
+
+impl DesktopNotifier {
+    /// 连接会话总线
+    pub async fn connect() -> Result<Self> {
+        let connection = Connection::session()
+            .await
+            .map_err(|e| VoiceInputError::Notification(format!("连接会话总线失败: {}", e)))?;
+
+        Ok(Self {
+            connection,
+            id: Arc::new(AtomicU32::new(0)),
+        })
+    }
+
+    /// 提示开始录音
+    pub async fn recording_started(&self) -> Result<()> {
+        self.notify("🎤 正在录音", "请开始说话…", &[], 1, -1).await
+    }
+
+    /// 显示识别结果预览
+    pub async fn result(&self, text: &str) -> Result<()> {
+        if text.is_empty() {
+            return self.notify("未识别到内容", "", &[], 1, 3000).await;
+        }
+        self.notify("✅ 已复制到剪贴板", &preview(text), &[], 1, 5000).await
+    }
+
+    /// 显示写入文件的结果预览
+    pub async fn saved_to_file(&self, text: &str, path: &Path) -> Result<()> {
+        let body = format!("{}\n{}", path.display(), preview(text));
+        self.notify("✅ 已写入文件", &body, &[], 1, 5000).await
+    }
+
+    /// 显示错误信息
+    pub async fn error(&self, error: &VoiceInputError) -> Result<()> {
+        self.notify("❌ 语音输入失败", &error.user_message(), &[], 2, -1).await
+    }
+
+    /// 显示已执行的语音命令
+    pub async fn command_executed(&self, phrase: &str, action: &str) -> Result<()> {
+        self.notify(&format!("▶️ {}", phrase), &preview(action), &[], 1, 5000).await
+    }
+
+    /// 请求确认：通知上显示 "执行" 和 "取消" 按钮，等待用户点击
+    ///
+    /// 点击 "取消"、关闭通知或超时都视为拒绝。
+    pub async fn confirm(&self, summary: &str, body: &str, timeout: Duration) -> Result<bool> {
+        // 先订阅信号，避免错过很快的点击
+        let rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface("org.freedesktop.Notifications")
+            .map_err(notification_error)?
+            .build();
+        let mut signals = MessageStream::for_match_rule(rule, &self.connection, None)
+            .await
+            .map_err(notification_error)?;
+
+        self.notify(summary, body, &["run", "执行", "cancel", "取消"], 2, 0).await?;
+        let id = self.id.load(Ordering::SeqCst);
+
+        let answer = tokio::time::timeout(timeout, async {
+            while let Some(message) = signals.next().await {
+                let Ok(message) = message else { continue };
+                let header = message.header();
+                match header.member().map(|m| m.as_str()) {
+                    Some("ActionInvoked") => {
+                        if let Ok((notification, action)) = message.body().deserialize::<(u32, String)>() {
+                            if notification == id {
+                                return action == "run";
+                            }
+                        }
+                    }
+                    Some("NotificationClosed") => {
+                        if let Ok((notification, _)) = message.body().deserialize::<(u32, u32)>() {
+                            if notification == id {
+                                return false;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            false
+        })
+        .await
+        .unwrap_or(false);
+        Ok(answer)
+    }
+
+    /// 发送（或替换）通知
+    ///
+    /// `actions`：按钮（标识、文字交替）；`urgency`：0 低，1 普通，2 紧急；
+    /// `expire_timeout`：毫秒，-1 由服务器决定，0 不自动消失
+    async fn notify(
+        &self,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        urgency: u8,
+        expire_timeout: i32,
+    ) -> Result<()> {
+        let mut hints: HashMap<&str, Value> = HashMap::new();
+        hints.insert("urgency", Value::U8(urgency));
+
+        let reply = self
+            .connection
+            .call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "Notify",
+                &(
+                    APP_NAME,
+                    self.id.load(Ordering::SeqCst),
+                    APP_ICON,
+                    summary,
+                    body,
+                    actions,
+                    hints,
+                    expire_timeout,
+                ),
+            )
+            .await
+            .map_err(|e| VoiceInputError::Notification(format!("发送通知失败: {}", e)))?;
+
+        let id: u32 = reply
+            .body()
+            .deserialize()
+            .map_err(|e| VoiceInputError::Notification(format!("解析通知 ID 失败: {}", e)))?;
+        self.id.store(id, Ordering::SeqCst);
+
+        Ok(())
+    }
+}
+
+fn notification_error(e: zbus::Error) -> VoiceInputError {
+    VoiceInputError::Notification(e.to_string())
+}
+
+/// 截取结果预览
+fn preview(text: &str) -> String {
+    let mut chars = text.chars();
+    let head: String = chars.by_ref().take(PREVIEW_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}…", head)
+    } else {
+        head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(preview("你好"), "你好");
+
+        let long = "语".repeat(PREVIEW_CHARS + 5);
+        let short = preview(&long);
+        assert!(short.ends_with('…'));
+        assert_eq!(short.chars().count(), PREVIEW_CHARS + 1);
+    }
+}
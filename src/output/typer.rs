@@ -0,0 +1,171 @@
+use std::process::{Command, Stdio};
+
+use crate::utils::{Result, VoiceInputError};
+
+/// 键盘模拟输出（向当前焦点窗口输入文字）
+///
+/// 支持的工具：
+/// - `xdotool`：X11
+/// - `wtype`：Wayland（wlroots 系列合成器）
+/// - `ydotool`：X11 / Wayland（需要 ydotoold 服务）
+pub struct TypingOutput {
+    tool: String,
+}
+
+impl TypingOutput {
+    /// 创建新的键盘模拟输出
+    pub fn new(tool: &str) -> Result<Self> {
+        if !matches!(tool, "xdotool" | "wtype" | "ydotool") {
+            return Err(VoiceInputError::Typing(format!(
+                "不支持的键盘模拟工具: {}（可选: xdotool | wtype | ydotool）",
+                tool
+            )));
+        }
+
+        // 检查工具是否安装
+        Command::new("which")
+            .arg(tool)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .ok()
+            .filter(|status| status.success())
+            .ok_or_else(|| VoiceInputError::Typing(format!(
+                "未找到 {} 命令，请安装: sudo apt install {}",
+                tool, tool
+            )))?;
+
+        Ok(Self {
+            tool: tool.to_string(),
+        })
+    }
+
+    /// 输入一段文字
+    pub fn type_text(&mut self, text: &str) -> Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let args: Vec<&str> = match self.tool.as_str() {
+            "xdotool" => vec!["type", "--clearmodifiers", "--delay", "0", "--", text],
+            "wtype" => vec!["--", text],
+            _ => vec!["type", "--", text],
+        };
+        self.run(&args)
+    }
+
+    /// 删除光标前的若干个字符
+    pub fn backspace(&mut self, count: usize) -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+
+        match self.tool.as_str() {
+            "xdotool" => {
+                let mut args = vec!["key", "--clearmodifiers", "--delay", "0"];
+                args.extend(std::iter::repeat_n("BackSpace", count));
+                self.run(&args)
+            }
+            "wtype" => {
+                let mut args = Vec::with_capacity(count * 2);
+                for _ in 0..count {
+                    args.extend(["-k", "BackSpace"]);
+                }
+                self.run(&args)
+            }
+            _ => {
+                // ydotool 使用 Linux 键码：14 为 BackSpace
+                let mut args = vec!["key"];
+                for _ in 0..count {
+                    args.extend(["14:1", "14:0"]);
+                }
+                self.run(&args)
+            }
+        }
+    }
+
+    fn run(&self, args: &[&str]) -> Result<()> {
+        let status = Command::new(&self.tool)
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|e| VoiceInputError::Typing(format!("启动 {} 失败: {}", self.tool, e)))?;
+
+        if !status.success() {
+            return Err(VoiceInputError::Typing(format!("{} 执行失败", self.tool)));
+        }
+
+        Ok(())
+    }
+}
+
+/// 流式输入器
+///
+/// 记录已经输入到窗口中的文字，每次收到新的文本时只输入差异部分；
+/// 如果之前输入的内容被动态修正改写，先用退格删除到公共前缀再重新输入。
+pub struct StreamingTyper {
+    output: TypingOutput,
+    typed: String,
+}
+
+impl StreamingTyper {
+    /// 创建新的流式输入器
+    pub fn new(output: TypingOutput) -> Self {
+        Self {
+            output,
+            typed: String::new(),
+        }
+    }
+
+    /// 将窗口中的文字更新为 `text`
+    pub fn update(&mut self, text: &str) -> Result<()> {
+        let (backspaces, insert) = diff_edit(&self.typed, text);
+        if backspaces == 0 && insert.is_empty() {
+            return Ok(());
+        }
+
+        tracing::debug!("流式输入: 退格 {} 个字符，输入 {:?}", backspaces, insert);
+        self.output.backspace(backspaces)?;
+        self.output.type_text(insert)?;
+        self.typed = text.to_string();
+
+        Ok(())
+    }
+
+    /// 已经输入的文字
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+}
+
+/// 计算从 `old` 变为 `new` 需要的退格数和追加文本
+fn diff_edit<'a>(old: &str, new: &'a str) -> (usize, &'a str) {
+    let common_bytes: usize = old
+        .chars()
+        .zip(new.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+
+    let backspaces = old[common_bytes..].chars().count();
+    (backspaces, &new[common_bytes..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_append() {
+        assert_eq!(diff_edit("今天", "今天天气"), (0, "天气"));
+        assert_eq!(diff_edit("", "你好"), (0, "你好"));
+    }
+
+    #[test]
+    fn test_diff_correction() {
+        assert_eq!(diff_edit("今天天7", "今天天气很好"), (1, "气很好"));
+        assert_eq!(diff_edit("abc", "xyz"), (3, "xyz"));
+        assert_eq!(diff_edit("你好", "你好"), (0, ""));
+    }
+}
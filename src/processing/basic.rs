@@ -0,0 +1,62 @@
+use super::TextProcessor;
+
+/// 去掉首尾空白
+pub struct Trim;
+
+impl TextProcessor for Trim {
+    fn name(&self) -> &'static str {
+        "trim"
+    }
+
+    fn process(&self, text: &str) -> String {
+        text.trim().to_string()
+    }
+}
+
+/// 短语去掉末尾句号
+///
+/// 搜索框、文件名、聊天短句里通常不需要句号。
+pub struct StripTrailingPeriod {
+    max_chars: usize,
+}
+
+impl StripTrailingPeriod {
+    pub fn new(max_chars: usize) -> Self {
+        Self { max_chars }
+    }
+}
+
+impl TextProcessor for StripTrailingPeriod {
+    fn name(&self) -> &'static str {
+        "strip_trailing_period"
+    }
+
+    fn process(&self, text: &str) -> String {
+        let Some(body) = text.strip_suffix('。').or_else(|| text.strip_suffix('.')) else {
+            return text.to_string();
+        };
+
+        // 省略号（…… / ...）不是句号
+        if body.ends_with('.') || body.chars().count() > self.max_chars {
+            return text.to_string();
+        }
+        body.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_trailing_period() {
+        let stage = StripTrailingPeriod::new(4);
+        assert_eq!(stage.process("好的。"), "好的");
+        assert_eq!(stage.process("OK."), "OK");
+        assert_eq!(stage.process("今天天气很好。"), "今天天气很好。");
+        assert_eq!(stage.process("等等..."), "等等...");
+        // 标点规范化之后的中文省略号
+        assert_eq!(stage.process("等等……"), "等等……");
+        assert_eq!(stage.process("好的"), "好的");
+    }
+}
@@ -0,0 +1,101 @@
+use super::{is_han, TextProcessor};
+
+/// 后面不算句子结束的缩写
+const ABBREVIATIONS: &[&str] = &["e.g.", "i.e.", "vs.", "mr.", "mrs.", "ms.", "dr."];
+
+/// 英文句首大写，单独的 i 写成 I
+///
+/// 只处理不含汉字的结果，中英混排的文字保持原样。
+pub struct Capitalize;
+
+impl TextProcessor for Capitalize {
+    fn name(&self) -> &'static str {
+        "capitalize"
+    }
+
+    fn process(&self, text: &str) -> String {
+        if text.chars().any(is_han) {
+            return text.to_string();
+        }
+
+        let chars: Vec<char> = text.chars().collect();
+        let mut result = String::with_capacity(text.len());
+        // 下一个字母是句首
+        let mut sentence_start = true;
+        // 刚遇到句末标点，后面是空白才算句子结束（排除 3.5、example.com）
+        let mut after_stop = false;
+        // 当前单词（用于识别缩写）
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let abbreviation = ABBREVIATIONS.contains(&word.to_lowercase().as_str());
+            if c == '\n' || (after_stop && c.is_whitespace() && !abbreviation) {
+                sentence_start = true;
+            }
+            after_stop = matches!(c, '.' | '!' | '?');
+            if c.is_whitespace() {
+                word.clear();
+            } else {
+                word.push(c);
+            }
+
+            let upper = if c.is_alphabetic() && sentence_start {
+                true
+            } else {
+                c == 'i' && is_pronoun_i(&chars, i)
+            };
+            if upper {
+                result.extend(c.to_uppercase());
+            } else {
+                result.push(c);
+            }
+            if c.is_alphanumeric() {
+                sentence_start = false;
+            }
+        }
+        result
+    }
+}
+
+/// 第 `i` 个字符是否为单独的 "i"（包括 i'm、i'll，不包括 i.e.）
+fn is_pronoun_i(chars: &[char], i: usize) -> bool {
+    let before = i.checked_sub(1).map(|j| chars[j]);
+    let after = chars.get(i + 1).copied();
+    if before.is_some_and(|c| c.is_alphanumeric() || c == '\'') {
+        return false;
+    }
+    match after {
+        None => true,
+        Some('.') => !chars.get(i + 2).is_some_and(|c| c.is_alphabetic()),
+        Some(c) => !c.is_alphanumeric(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentence_case() {
+        assert_eq!(
+            Capitalize.process("hello world. how are you? fine!"),
+            "Hello world. How are you? Fine!"
+        );
+        assert_eq!(Capitalize.process("version 3.5 is out"), "Version 3.5 is out");
+        assert_eq!(Capitalize.process("first line\nsecond line"), "First line\nSecond line");
+    }
+
+    #[test]
+    fn test_pronoun_i() {
+        assert_eq!(
+            Capitalize.process("yes i think i'm right, so do i."),
+            "Yes I think I'm right, so do I."
+        );
+        assert_eq!(Capitalize.process("use it, i.e. the api"), "Use it, i.e. the api");
+        assert_eq!(Capitalize.process("ikea is big"), "Ikea is big");
+    }
+
+    #[test]
+    fn test_chinese_untouched() {
+        assert_eq!(Capitalize.process("我们用 rust. i think"), "我们用 rust. i think");
+    }
+}
@@ -0,0 +1,241 @@
+use regex::Regex;
+
+use super::TextProcessor;
+
+/// 只在开头且后面跟着逗号时才算口头禅的词（"那个，我们…" 去掉，"那个文件" 保留）
+const DISCOURSE: &[&str] = &[
+    "怎么说呢", "就是说", "然后呢", "那个", "这个", "就是", "额", "啊", "哦", "you know", "I mean", "like",
+];
+
+/// 结巴时容易重复的字（"我我我觉得" → "我觉得"；"谢谢"、"看看" 等叠词不受影响）
+///
+/// 连着说两遍可能是正常用法（"会会开很久"、"把把手擦干净"），
+/// 所以只合并三遍以上，或中间夹着逗号的重复。
+const STUTTER_ZH: &[char] = &[
+    '我', '你', '您', '他', '她', '它', '咱', '这', '那', '就', '在', '要', '把', '被', '给', '跟', '也', '还', '都',
+    '会',
+];
+
+/// 英文中容易重复的虚词（"I I think" → "I think"；"very very" 等强调不受影响）
+const STUTTER_EN: &[&str] = &[
+    "i", "you", "he", "she", "it", "we", "they", "the", "a", "an", "to", "and", "but", "of", "in", "on", "for",
+    "my", "this", "what",
+];
+
+/// 去掉语气词和口吃重复
+///
+/// - "嗯"、"呃"、"uh"、"um" 等纯语气词在任何位置都去掉
+/// - "那个"、"就是说"、"you know" 等只在整段话开头且后面有逗号时去掉
+/// - 代词、虚词的口吃重复合并为一个
+pub struct Disfluency {
+    hesitation_zh: Regex,
+    hesitation_en: Regex,
+    discourse: Regex,
+    word: Regex,
+    /// 删除后整理标点和空格
+    tidy: Vec<(Regex, &'static str)>,
+    repeats: bool,
+}
+
+impl Disfluency {
+    /// `fillers` 为额外的口头禅，规则同 "那个"
+    pub fn new(fillers: &[String], repeats: bool) -> Self {
+        let mut words: Vec<String> = DISCOURSE.iter().map(|word| regex::escape(word)).collect();
+        words.extend(fillers.iter().filter(|word| !word.is_empty()).map(|word| regex::escape(word)));
+        words.sort_by_key(|word| std::cmp::Reverse(word.chars().count()));
+        Self {
+            // "唔" 在粤语中是 "不"（唔该、唔好），不算语气词
+            hesitation_zh: Regex::new(r"[嗯呃]+[，,、]?\s*").unwrap(),
+            hesitation_en: Regex::new(r"\b(?:[Uu]+h+|[Uu]+m+|[Uu]hm|[Ee]+r+m*|[Hh]m+|[Mm]m+)\b[，,]?\s*").unwrap(),
+            discourse: Regex::new(&format!(r"(?i)^(?:{})[，,、]\s*", words.join("|"))).unwrap(),
+            word: Regex::new(r"[A-Za-z']+").unwrap(),
+            tidy: [
+                (r"[，,、]\s*([。！？.!?])", "$1"),
+                (r"([，、])[，,、]+", "$1"),
+                (r" {2,}", " "),
+                (r" +([,.!?])", "$1"),
+            ]
+            .into_iter()
+            .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+            .collect(),
+            repeats,
+        }
+    }
+
+    /// 合并英文虚词的连续重复（"the the" → "the"）
+    fn collapse_english(&self, text: &str) -> String {
+        let words: Vec<_> = self.word.find_iter(text).collect();
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for pair in words.windows(2) {
+            let (first, second) = (pair[0], pair[1]);
+            let gap = &text[first.end()..second.start()];
+            let lower = first.as_str().to_ascii_lowercase();
+            if lower == second.as_str().to_ascii_lowercase()
+                && STUTTER_EN.contains(&lower.as_str())
+                && gap.trim_start_matches(',').trim().is_empty()
+                && first.start() >= last
+            {
+                result.push_str(&text[last..first.start()]);
+                last = second.start();
+            }
+        }
+        result.push_str(&text[last..]);
+        result
+    }
+
+    /// 删除语气词后整理标点和空格
+    fn tidy(&self, text: &str) -> String {
+        let text = text.trim_start_matches(['，', ',', '、', ' ']).to_string();
+        let text = self.tidy.iter().fold(text, |text, (pattern, replacement)| {
+            pattern.replace_all(&text, *replacement).into_owned()
+        });
+        text.trim_end_matches(' ').to_string()
+    }
+}
+
+impl TextProcessor for Disfluency {
+    fn name(&self) -> &'static str {
+        "disfluency"
+    }
+
+    fn process(&self, text: &str) -> String {
+        let mut result = self.hesitation_zh.replace_all(text, "").into_owned();
+        result = self.hesitation_en.replace_all(&result, "").into_owned();
+
+        // 连续的口头禅（"啊，那个，就是说，"）需要多次匹配开头
+        loop {
+            let next = self.discourse.replace(&result, "").into_owned();
+            if next == result {
+                break;
+            }
+            result = next;
+        }
+
+        if self.repeats {
+            result = self.collapse_english(&collapse_chinese(&result));
+        }
+        if result == text {
+            result
+        } else {
+            self.tidy(&result)
+        }
+    }
+}
+
+/// 合并中文的口吃重复：以易重复字开头的 1～4 字片段
+///
+/// - 紧接着重复三遍以上时只保留一个（"我我我觉得"）
+/// - 中间夹着逗号重复一遍、接着说下去时删掉前一个（"我，我觉得"）；
+///   后面又是逗号的是强调或列举（"我的，我的，都是我的"），保持不变
+fn collapse_chinese(text: &str) -> String {
+    let is_comma = |c: Option<&char>| matches!(c, Some('，' | ',' | '、'));
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if STUTTER_ZH.contains(&chars[i]) {
+            let repeated = (1..=4).rev().find_map(|len| {
+                let word = chars.get(i..i + len)?;
+                let copies = (1..)
+                    .take_while(|n| chars.get(i + n * len..i + (n + 1) * len) == Some(word))
+                    .count()
+                    + 1;
+                if copies >= 3 {
+                    return Some(i + (copies - 1) * len);
+                }
+
+                let mut next = i + len;
+                if !is_comma(chars.get(next)) {
+                    return None;
+                }
+                next += 1;
+                while chars.get(next) == Some(&' ') {
+                    next += 1;
+                }
+                let after = next + len;
+                (chars.get(next..after) == Some(word) && after < chars.len() && !is_comma(chars.get(after)))
+                    .then_some(next)
+            });
+            if let Some(next) = repeated {
+                i = next;
+                continue;
+            }
+        }
+        result.push(chars[i]);
+        i += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disfluency() -> Disfluency {
+        Disfluency::new(&[], true)
+    }
+
+    #[test]
+    fn test_hesitations() {
+        let d = disfluency();
+        assert_eq!(d.process("嗯，我觉得可以"), "我觉得可以");
+        assert_eq!(d.process("我觉得，呃，还是算了"), "我觉得，还是算了");
+        assert_eq!(d.process("好的嗯。"), "好的。");
+        assert_eq!(d.process("Um, I think we should uh go."), "I think we should go.");
+        assert_eq!(d.process("So, uh, what now?"), "So, what now?");
+    }
+
+    #[test]
+    fn test_discourse_fillers() {
+        let d = disfluency();
+        assert_eq!(d.process("那个，我们明天开会"), "我们明天开会");
+        assert_eq!(d.process("啊，那个，就是说，价格太高了"), "价格太高了");
+        // 只去掉开头的口头禅
+        assert_eq!(d.process("我想，就是说，换一个方案"), "我想，就是说，换一个方案");
+        assert_eq!(d.process("You know, it works."), "it works.");
+    }
+
+    #[test]
+    fn test_legitimate_uses_untouched() {
+        let d = disfluency();
+        for text in [
+            "把那个文件发给我",
+            "这个方案就是说不通",
+            "也就是说我们要延期",
+            "金额是多少",
+            "好啊，走吧",
+            "我们谢谢大家，看看再说",
+            "一个一个地检查",
+            "研究研究再定",
+            "明天的会会开很久",
+            "把把手擦干净",
+            "给给钱的人打电话",
+            "这是我的，我的，都是我的",
+            "你要哪个？这个，还是那个？",
+            "唔该",
+            "我们我们先看一下",
+            "I really really like it.",
+            "The number is umbrella-shaped.",
+        ] {
+            assert_eq!(d.process(text), text);
+        }
+    }
+
+    #[test]
+    fn test_repeats() {
+        let d = disfluency();
+        assert_eq!(d.process("我我我觉得不错"), "我觉得不错");
+        assert_eq!(d.process("我，我觉得不错"), "我觉得不错");
+        assert_eq!(d.process("我们我们我们先看一下"), "我们先看一下");
+        assert_eq!(d.process("我们，我们先看一下"), "我们先看一下");
+        assert_eq!(d.process("I I think the the plan works"), "I think the plan works");
+        assert_eq!(Disfluency::new(&[], false).process("我我我觉得"), "我我我觉得");
+    }
+
+    #[test]
+    fn test_custom_fillers() {
+        let d = Disfluency::new(&["对吧".to_string()], true);
+        assert_eq!(d.process("对吧，我们先这样"), "我们先这样");
+    }
+}
@@ -0,0 +1,474 @@
+use regex::{Captures, Regex};
+
+use super::Classes;
+use crate::config::ItnClass;
+
+/// 中文数词（可带小数："三点五"）
+///
+/// 小数部分后面紧跟的位值也一起匹配（"一点三十"），这样的数不是小数，
+/// 整体解析失败后保持原样，而不会被拆成 "1.3" 和 "10"。
+const NUM: &str = "[零〇一二两三四五六七八九十百千万亿]+(?:点[零〇一二三四五六七八九]+[十百千万亿]*)?";
+
+/// 含有数字但不表示数量的固定搭配
+const IDIOMS: &[&str] = &[
+    "十分",
+    "十足",
+    "十字",
+    "十全十美",
+    "十有八九",
+    "十之八九",
+    "十拿九稳",
+    "十万火急",
+    "一五一十",
+    "三七二十一",
+    "一目十行",
+    "九九八十一",
+    "三十而立",
+    "四十不惑",
+    "五十知天命",
+    "一万年",
+    "万一",
+];
+
+/// 表示 "最先" 而不是名次的序数词
+const ORDINAL_PHRASES: &[&str] = &[
+    "第一次",
+    "第一时间",
+    "第一印象",
+    "第一眼",
+    "第一手",
+    "第一线",
+    "第一步",
+    "第一天",
+    "第二天",
+];
+
+/// 中文反向文本规范化：把中文数词写成阿拉伯数字
+pub struct ChineseItn {
+    date: Regex,
+    year: Regex,
+    time: Regex,
+    percent: Regex,
+    currency: Regex,
+    unit: Regex,
+    ordinal: Regex,
+    phone: Regex,
+    sequence: Regex,
+    cardinal: Regex,
+}
+
+impl Default for ChineseItn {
+    fn default() -> Self {
+        let regex = |pattern: &str| Regex::new(&pattern.replace("NUM", NUM)).expect("内置正则表达式有效");
+        Self {
+            date: regex(
+                r"(?:(?P<y>[零〇一二三四五六七八九]{2,4}|[一二两三四五六七八九十百千零]+)年)?(?P<m>十[一二]?|[一二三四五六七八九])月(?:(?P<d>三十一?|二十[一二三四五六七八九]?|十[一二三四五六七八九]?|[一二三四五六七八九])(?P<ds>[日号]))?",
+            ),
+            // 没有月份时只认四位的年份（"七八年"、"三五年后" 是约数）
+            year: regex(r"(?P<y>[零〇一二三四五六七八九]{4})年"),
+            time: regex(
+                r"(?P<p>凌晨|早上|早晨|上午|中午|下午|傍晚|晚上)?(?P<h>[零一二两三四五六七八九十]{1,3})点(?:(?P<half>半)|(?P<q>一刻|三刻)|(?P<o>整|钟)|(?P<m>[零一二三四五六七八九十]{1,3})分(?:(?P<s>[零一二三四五六七八九十]{1,3})秒)?|(?P<bare>零[一二三四五六七八九]|[一二三四五]十[一二三四五六七八九]?|十[一二三四五六七八九]?))?",
+            ),
+            percent: regex(r"百分之(?P<n>NUM)"),
+            currency: regex(
+                r"(?P<n>NUM)(?P<u>块|元|美元|美金|欧元|英镑|日元|港币|港元)(?:(?P<j>[一二两三四五六七八九])[毛角])?(?:零?(?P<f>[一二三四五六七八九])分)?(?P<tail>[一二三四五六七八九])?(?P<qian>钱)?",
+            ),
+            unit: regex(
+                r"(?P<n>NUM)(?P<u>平方公里|平方米|立方米|公里|千米|厘米|毫米|公分|米|公斤|千克|毫克|克|吨|毫升|升|摄氏度|千瓦|英里|英尺|英寸|个小时|小时|分钟|秒钟)",
+            ),
+            ordinal: regex(r"第(?P<n>[零〇一二两三四五六七八九十百千万亿]+)"),
+            phone: regex(r"[零〇一二三四五六七八九幺](?: ?[零〇一二三四五六七八九幺]){6,}"),
+            sequence: regex(r"[零〇一二三四五六七八九幺]{3,}"),
+            cardinal: regex(NUM),
+        }
+    }
+}
+
+impl ChineseItn {
+    pub fn process(&self, text: &str, classes: &Classes) -> String {
+        let mut text = text.to_string();
+        let mut apply = |class: ItnClass, regex: &Regex, convert: &dyn Fn(&Captures, &str) -> Option<String>| {
+            if classes.contains(class) {
+                let source = text.clone();
+                text = regex
+                    .replace_all(&source, |caps: &Captures| {
+                        let matched = caps.get(0).expect("整体匹配总是存在");
+                        if in_fixed_phrase(&source, matched.start(), matched.end()) {
+                            return caps[0].to_string();
+                        }
+                        convert(caps, &source).unwrap_or_else(|| caps[0].to_string())
+                    })
+                    .into_owned();
+            }
+        };
+
+        apply(ItnClass::Date, &self.date, &|caps, _| convert_date(caps));
+        apply(ItnClass::Date, &self.year, &|caps, source| {
+            Some(format!("{}年", spoken_digits(caps.name("y")?, source)?))
+        });
+        apply(ItnClass::Time, &self.time, &|caps, _| convert_time(caps));
+        apply(ItnClass::Percent, &self.percent, &|caps, _| Some(format!("{}%", parse_number(&caps["n"])?)));
+        apply(ItnClass::Currency, &self.currency, &|caps, _| convert_currency(caps));
+        apply(ItnClass::Unit, &self.unit, &|caps, source| convert_unit(caps, source));
+        apply(ItnClass::Ordinal, &self.ordinal, &|caps, _| Some(format!("第{}", parse_integer(&caps["n"])?)));
+        apply(ItnClass::Phone, &self.phone, &|caps, _| parse_sequence(&caps[0].replace(' ', "")));
+        apply(ItnClass::Cardinal, &self.sequence, &|caps, source| convert_sequence(caps, source));
+        apply(ItnClass::Cardinal, &self.cardinal, &|caps, source| convert_cardinal(caps, source));
+
+        text
+    }
+}
+
+/// 二零二六年十月十八号 → 2026年10月18号
+fn convert_date(caps: &Captures) -> Option<String> {
+    let mut output = String::new();
+    if let Some(year) = caps.name("y") {
+        let year = parse_sequence(year.as_str()).or_else(|| parse_integer(year.as_str()).map(|y| y.to_string()))?;
+        output.push_str(&format!("{}年", year));
+    }
+
+    let month = parse_integer(&caps["m"])?;
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+    output.push_str(&format!("{}月", month));
+
+    if let (Some(day), Some(suffix)) = (caps.name("d"), caps.name("ds")) {
+        let day = parse_integer(day.as_str())?;
+        if !(1..=31).contains(&day) {
+            return None;
+        }
+        output.push_str(&format!("{}{}", day, suffix.as_str()));
+    }
+    Some(output)
+}
+
+/// 下午三点半 → 下午3:30
+fn convert_time(caps: &Captures) -> Option<String> {
+    let prefix = caps.name("p").map_or("", |p| p.as_str());
+    let hour = parse_integer(&caps["h"])?;
+    if hour > 24 {
+        return None;
+    }
+
+    let minute = |name: &str| -> Option<Option<u64>> {
+        match caps.name(name) {
+            Some(m) => parse_integer(m.as_str()).filter(|&m| m < 60).map(Some),
+            None => Some(None),
+        }
+    };
+
+    let time = if caps.name("half").is_some() {
+        format!("{}:30", hour)
+    } else if let Some(quarter) = caps.name("q") {
+        format!("{}:{}", hour, if quarter.as_str() == "一刻" { 15 } else { 45 })
+    } else if let Some(o) = caps.name("o") {
+        if o.as_str() == "整" {
+            format!("{}:00", hour)
+        } else {
+            format!("{}点钟", hour)
+        }
+    } else if let Some(m) = minute("m")? {
+        match minute("s")? {
+            Some(s) => format!("{}:{:02}:{:02}", hour, m, s),
+            None => format!("{}:{:02}", hour, m),
+        }
+    } else if prefix.is_empty() {
+        // 没有上午 / 下午等前缀时 "三点" "三点五" 可能不是时间
+        return None;
+    } else if let Some(m) = minute("bare")? {
+        format!("{}:{:02}", hour, m)
+    } else {
+        format!("{}点", hour)
+    };
+    Some(format!("{}{}", prefix, time))
+}
+
+/// 三百五十块 → 350元，三块五毛钱 → 3.5元
+fn convert_currency(caps: &Captures) -> Option<String> {
+    let unit = &caps["u"];
+    let single = |name: &str| caps.name(name).and_then(|m| m.as_str().chars().next()).and_then(digit);
+    let (jiao, fen, tail) = (single("j"), single("f"), single("tail"));
+
+    // "一块" "两块" 也可能是量词，只有带 "钱" 或角分时才算金额
+    if unit == "块" && caps.name("qian").is_none() && jiao.is_none() && fen.is_none() && tail.is_none() {
+        return None;
+    }
+
+    let amount = parse_number(&caps["n"])?;
+    // 三块五 → 五角；三块五毛五 → 五分
+    let (jiao, fen) = match (jiao, fen, tail) {
+        (None, None, Some(tail)) => (Some(tail), None),
+        (Some(jiao), None, Some(tail)) => (Some(jiao), Some(tail)),
+        (jiao, fen, _) => (jiao, fen),
+    };
+
+    let amount = match (jiao, fen) {
+        _ if amount.contains('.') => amount,
+        (None, None) => amount,
+        (jiao, None) => format!("{}.{}", amount, jiao.unwrap_or(0)),
+        (jiao, Some(fen)) => format!("{}.{}{}", amount, jiao.unwrap_or(0), fen),
+    };
+    let unit = if unit == "块" { "元" } else { unit };
+    Some(format!("{}{}", amount, unit))
+}
+
+/// 五公里 → 5公里（时间单位只转换十以上的数）
+fn convert_unit(caps: &Captures, source: &str) -> Option<String> {
+    // 一米八、两米三：单位后面省略了小数部分
+    let end = caps.get(0)?.end();
+    if source[end..].starts_with(|c: char| digit(c).is_some()) {
+        return None;
+    }
+    let unit = &caps["u"];
+    let number = parse_number(&caps["n"])?;
+    let is_duration = matches!(unit, "个小时" | "小时" | "分钟" | "秒钟");
+    if is_duration && !number.contains('.') && number.parse::<u64>().ok()? < 10 {
+        return None;
+    }
+    Some(format!("{}{}", number, unit))
+}
+
+/// 逐位读出的数字：三零五 → 305
+fn convert_sequence(caps: &Captures, source: &str) -> Option<String> {
+    spoken_digits(caps.get(0)?, source)
+}
+
+/// 逐位读出的数字（年份、编号），排除数数、约数和星期
+fn spoken_digits(matched: regex::Match, source: &str) -> Option<String> {
+    let before = &source[..matched.start()];
+    // 紧挨着位值的是按位值读的数（九九八十一、十七八年），不是逐位读出的
+    let is_unit = |c: char| matches!(c, '十' | '百' | '千' | '万' | '亿');
+    if before.ends_with(is_unit) || source[matched.end()..].starts_with(is_unit) {
+        return None;
+    }
+    // 星期一三五
+    if ["星期", "礼拜", "周"].iter().any(|prefix| before.ends_with(prefix)) {
+        return None;
+    }
+    let digits: Vec<u64> = matched.as_str().chars().filter_map(digit).collect();
+    // 一二三、三四五：数数；七八：约数
+    if digits.windows(2).all(|pair| pair[1] == pair[0] + 1) {
+        return None;
+    }
+    parse_sequence(matched.as_str())
+}
+
+/// 独立的数词：只转换十以上的数和小数，"一个" "三天" 保持不变
+fn convert_cardinal(caps: &Captures, source: &str) -> Option<String> {
+    let matched = caps.get(0)?;
+    let text = matched.as_str();
+    let (start, end) = (matched.start(), matched.end());
+
+    // 以 百 / 千 / 万 开头的多是普通词语（千万、万一、百姓）
+    if !text.starts_with(|c: char| digit(c).is_some() || c == '十') {
+        return None;
+    }
+    // 十几、几十
+    if source[..start].ends_with('几') || source[end..].starts_with('几') {
+        return None;
+    }
+
+    let number = parse_number(text)?;
+    if !number.contains('.') && number.parse::<u64>().ok()? < 10 {
+        return None;
+    }
+    Some(number)
+}
+
+/// 匹配位于固定搭配（成语、表示 "最先" 的序数词）之内
+fn in_fixed_phrase(source: &str, start: usize, end: usize) -> bool {
+    IDIOMS.iter().chain(ORDINAL_PHRASES).any(|phrase| {
+        source
+            .match_indices(phrase)
+            .any(|(position, _)| position <= start && end <= position + phrase.len())
+    })
+}
+
+fn digit(c: char) -> Option<u64> {
+    Some(match c {
+        '零' | '〇' => 0,
+        '一' | '幺' => 1,
+        '二' | '两' => 2,
+        '三' => 3,
+        '四' => 4,
+        '五' => 5,
+        '六' => 6,
+        '七' => 7,
+        '八' => 8,
+        '九' => 9,
+        _ => return None,
+    })
+}
+
+/// 逐位读出的数字：二零二六 → 2026
+fn parse_sequence(text: &str) -> Option<String> {
+    text.chars()
+        .map(|c| match c {
+            '两' => None,
+            c => digit(c).map(|d| char::from(b'0' + d as u8)),
+        })
+        .collect()
+}
+
+/// 带小数的数：三点一四 → 3.14
+fn parse_number(text: &str) -> Option<String> {
+    match text.split_once('点') {
+        Some((integer, fraction)) => Some(format!("{}.{}", parse_integer(integer)?, parse_sequence(fraction)?)),
+        None => parse_integer(text).map(|n| n.to_string()),
+    }
+}
+
+/// 按位值读出的整数：三百零五 → 305，一万五 → 15000
+fn parse_integer(text: &str) -> Option<u64> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut total: u64 = 0;
+    let mut section: u64 = 0;
+    let mut pending: Option<u64> = None;
+    // 当前小节内上一个位值（十 / 百 / 千 必须递减）
+    let mut section_unit: u64 = 0;
+
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            '零' | '〇' => pending = None,
+            '十' | '百' | '千' => {
+                let unit = match c {
+                    '十' => 10,
+                    '百' => 100,
+                    _ => 1000,
+                };
+                if section_unit != 0 && unit >= section_unit {
+                    return None;
+                }
+                let n = match pending.take() {
+                    Some(n) => n,
+                    // 十二、百分之百
+                    None if i == 0 || unit == 10 => 1,
+                    None => return None,
+                };
+                section = section.checked_add(n.checked_mul(unit)?)?;
+                section_unit = unit;
+            }
+            '万' | '亿' => {
+                let unit: u64 = if c == '万' { 10_000 } else { 100_000_000 };
+                section = section.checked_add(pending.take().unwrap_or(0))?;
+                if section == 0 && i == 0 {
+                    section = 1;
+                }
+                total = if c == '万' {
+                    total.checked_add(section.checked_mul(unit)?)?
+                } else {
+                    total.checked_add(section)?.checked_mul(unit)?
+                };
+                section = 0;
+                section_unit = 0;
+            }
+            c => {
+                // 两个数字连在一起（三三）不是按位值读的数
+                if pending.is_some() {
+                    return None;
+                }
+                pending = Some(digit(c)?);
+            }
+        }
+    }
+
+    if let Some(n) = pending {
+        // 三百五 → 350：末尾的数字省略了下一级位值
+        let previous = chars.len().checked_sub(2).map(|i| chars[i]);
+        let scale = match previous {
+            Some('百') => 10,
+            Some('千') => 100,
+            Some('万') => 1000,
+            Some('亿') => 10_000_000,
+            _ => 1,
+        };
+        section = section.checked_add(n.checked_mul(scale)?)?;
+    }
+    total.checked_add(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn itn(text: &str) -> String {
+        ChineseItn::default().process(text, &Classes::all())
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_integer("十二"), Some(12));
+        assert_eq!(parse_integer("三百零五"), Some(305));
+        assert_eq!(parse_integer("三百五"), Some(350));
+        assert_eq!(parse_integer("一万五"), Some(15000));
+        assert_eq!(parse_integer("两千零二十六"), Some(2026));
+        assert_eq!(parse_integer("一亿二千万"), Some(120_000_000));
+        assert_eq!(parse_integer("三三"), None);
+        assert_eq!(parse_number("三点一四").as_deref(), Some("3.14"));
+    }
+
+    #[test]
+    fn test_dates_and_times() {
+        assert_eq!(itn("二零二六年十月十八号下午三点半"), "2026年10月18号下午3:30");
+        assert_eq!(itn("一九九八年"), "1998年");
+        assert_eq!(itn("九九年十月"), "99年10月");
+        assert_eq!(itn("晚上八点十五开会"), "晚上8:15开会");
+        assert_eq!(itn("三点二十分出发"), "3:20出发");
+        assert_eq!(itn("十点整"), "10:00");
+        assert_eq!(itn("三点钟"), "3点钟");
+        // 没有时段前缀时不当作时间
+        assert_eq!(itn("有三点建议"), "有三点建议");
+    }
+
+    #[test]
+    fn test_money_percent_units() {
+        assert_eq!(itn("三百五十块"), "350块");
+        assert_eq!(itn("三百五十块钱"), "350元");
+        assert_eq!(itn("三块五"), "3.5元");
+        assert_eq!(itn("三块零五分"), "3.05元");
+        assert_eq!(itn("一百美元"), "100美元");
+        assert_eq!(itn("百分之三点五"), "3.5%");
+        assert_eq!(itn("百分之百"), "100%");
+        assert_eq!(itn("跑了五公里"), "跑了5公里");
+        assert_eq!(itn("等十分钟"), "等10分钟");
+        // 量词 "块" 不是金额
+        assert_eq!(itn("两块蛋糕"), "两块蛋糕");
+    }
+
+    #[test]
+    fn test_cardinal_ordinal_phone() {
+        assert_eq!(itn("第三名"), "第3名");
+        assert_eq!(itn("一共二十三个人"), "一共23个人");
+        assert_eq!(itn("圆周率约等于三点一四"), "圆周率约等于3.14");
+        assert_eq!(itn("电话幺三八一二三四五六七八"), "电话13812345678");
+        assert_eq!(itn("房间三零五"), "房间305");
+        // 个位数和固定搭配保持不变
+        assert_eq!(itn("一个人"), "一个人");
+        assert_eq!(itn("千万不要十分紧张"), "千万不要十分紧张");
+        assert_eq!(itn("十几个"), "十几个");
+    }
+
+    #[test]
+    fn test_prose_untouched() {
+        assert_eq!(itn("九九八十一难"), "九九八十一难");
+        assert_eq!(itn("三十而立"), "三十而立");
+        assert_eq!(itn("第一次见面"), "第一次见面");
+        assert_eq!(itn("一万年太久"), "一万年太久");
+        assert_eq!(itn("我一米八"), "我一米八");
+        assert_eq!(itn("他说了一二三"), "他说了一二三");
+        // 约数、星期和不是小数的 "点"
+        assert_eq!(itn("过了七八年"), "过了七八年");
+        assert_eq!(itn("三五年后"), "三五年后");
+        assert_eq!(itn("十七八年前"), "十七八年前");
+        assert_eq!(itn("九九年"), "九九年");
+        assert_eq!(itn("星期一三五上课"), "星期一三五上课");
+        assert_eq!(itn("一点三十出发"), "一点三十出发");
+    }
+
+    #[test]
+    fn test_disabled_classes() {
+        let classes = Classes::from(&[ItnClass::Percent][..]);
+        assert_eq!(ChineseItn::default().process("百分之五，二十个", &classes), "5%，二十个");
+    }
+}
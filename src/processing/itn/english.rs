@@ -0,0 +1,505 @@
+use regex::Regex;
+
+use super::Classes;
+use crate::config::ItnClass;
+
+const CARDINALS: &[(&str, u64)] = &[
+    ("zero", 0),
+    ("oh", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+    ("hundred", 100),
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+
+const ORDINALS: &[(&str, u64)] = &[
+    ("first", 1),
+    ("second", 2),
+    ("third", 3),
+    ("fourth", 4),
+    ("fifth", 5),
+    ("sixth", 6),
+    ("seventh", 7),
+    ("eighth", 8),
+    ("ninth", 9),
+    ("tenth", 10),
+    ("eleventh", 11),
+    ("twelfth", 12),
+    ("thirteenth", 13),
+    ("fourteenth", 14),
+    ("fifteenth", 15),
+    ("sixteenth", 16),
+    ("seventeenth", 17),
+    ("eighteenth", 18),
+    ("nineteenth", 19),
+    ("twentieth", 20),
+    ("thirtieth", 30),
+    ("fortieth", 40),
+    ("fiftieth", 50),
+    ("sixtieth", 60),
+    ("seventieth", 70),
+    ("eightieth", 80),
+    ("ninetieth", 90),
+    ("hundredth", 100),
+    ("thousandth", 1_000),
+    ("millionth", 1_000_000),
+];
+
+const MONTHS: &[&str] = &[
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// 度量单位：前面的数即使小于十也写成数字
+const UNITS: &[&str] = &[
+    "kilometers", "kilometres", "miles", "meters", "metres", "centimeters", "centimetres",
+    "millimeters", "millimetres", "feet", "foot", "inches", "inch", "kilograms", "kilos",
+    "grams", "pounds", "liters", "litres", "gallons", "degrees", "gigabytes", "megabytes",
+];
+
+/// 货币名称 → 符号
+const CURRENCIES: &[(&str, &str)] = &[
+    ("dollars", "$"),
+    ("dollar", "$"),
+    ("bucks", "$"),
+    ("euros", "€"),
+    ("euro", "€"),
+    ("yuan", "¥"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Digit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+/// 一个数词
+#[derive(Debug, Clone, Copy)]
+struct NumberWord {
+    value: u64,
+    kind: Kind,
+    ordinal: bool,
+}
+
+fn lookup(word: &str) -> Option<NumberWord> {
+    let (value, ordinal) = CARDINALS
+        .iter()
+        .find(|(w, _)| *w == word)
+        .map(|&(_, v)| (v, false))
+        .or_else(|| ORDINALS.iter().find(|(w, _)| *w == word).map(|&(_, v)| (v, true)))?;
+    let kind = match value {
+        0..=9 => Kind::Digit,
+        10..=19 => Kind::Teen,
+        20..=99 => Kind::Tens,
+        100 => Kind::Hundred,
+        _ => Kind::Scale,
+    };
+    Some(NumberWord { value, kind, ordinal })
+}
+
+/// 一串连续数词的解析结果
+///
+/// 不能合成一个数的部分拆成多段：`twenty twenty six` → [20, 26]，
+/// `five five five` → [5, 5, 5]，按顺序拼接即为年份或号码。
+#[derive(Debug, PartialEq)]
+struct Spoken {
+    chunks: Vec<u64>,
+    fraction: Option<String>,
+    ordinal: bool,
+}
+
+impl Spoken {
+    fn parse(words: &[&str]) -> Option<Self> {
+        let (integer, fraction) = match words.iter().position(|w| *w == "point") {
+            Some(i) => (&words[..i], Some(&words[i + 1..])),
+            None => (words, None),
+        };
+
+        let mut chunks = Vec::new();
+        let (mut total, mut small) = (0u64, 0u64);
+        let mut last: Option<Kind> = None;
+        let mut last_scale = u64::MAX;
+        let mut ordinal = false;
+
+        let integer: Vec<&str> = integer.iter().copied().filter(|w| *w != "and").collect();
+        for (i, word) in integer.iter().enumerate() {
+            let number = lookup(word)?;
+            if number.ordinal {
+                // 序数词只能在最后
+                if i + 1 != integer.len() || fraction.is_some() {
+                    return None;
+                }
+                ordinal = true;
+            }
+
+            let continues = match (last, number.kind) {
+                (None, _) => true,
+                (Some(Kind::Tens), Kind::Digit) => number.value != 0,
+                (Some(Kind::Digit | Kind::Teen | Kind::Tens), Kind::Hundred) => small < 100,
+                (Some(Kind::Hundred | Kind::Scale), Kind::Digit) => number.value != 0,
+                (Some(Kind::Hundred | Kind::Scale), Kind::Teen | Kind::Tens) => true,
+                (Some(_), Kind::Scale) => number.value < last_scale,
+                _ => false,
+            };
+            if !continues {
+                chunks.push(total + small);
+                (total, small, last_scale) = (0, 0, u64::MAX);
+            }
+
+            match number.kind {
+                Kind::Digit | Kind::Teen | Kind::Tens => small += number.value,
+                Kind::Hundred => small = small.max(1) * 100,
+                Kind::Scale => {
+                    total += small.max(1) * number.value;
+                    small = 0;
+                    last_scale = number.value;
+                }
+            }
+            last = Some(number.kind);
+        }
+        if last.is_some() {
+            chunks.push(total + small);
+        }
+
+        let fraction = match fraction {
+            Some(words) => {
+                let digits: Option<String> = words
+                    .iter()
+                    .map(|w| {
+                        lookup(w)
+                            .filter(|n| n.kind == Kind::Digit && !n.ordinal)
+                            .map(|n| char::from(b'0' + n.value as u8))
+                    })
+                    .collect();
+                Some(digits.filter(|d| !d.is_empty())?)
+            }
+            None => None,
+        };
+
+        if chunks.is_empty() {
+            return None;
+        }
+        Some(Self { chunks, fraction, ordinal })
+    }
+
+    /// 单个数（不是年份或号码时）
+    fn value(&self) -> Option<u64> {
+        match self.chunks[..] {
+            [value] => Some(value),
+            _ => None,
+        }
+    }
+
+    fn digits(&self) -> String {
+        let mut digits: String = self.chunks.iter().map(|c| c.to_string()).collect();
+        if let Some(fraction) = &self.fraction {
+            digits.push('.');
+            digits.push_str(fraction);
+        }
+        digits
+    }
+}
+
+/// 英文反向文本规范化：twenty three → 23，five percent → 5%
+pub struct EnglishItn {
+    word: Regex,
+    meridiem: Regex,
+}
+
+impl Default for EnglishItn {
+    fn default() -> Self {
+        Self {
+            word: Regex::new(r"[A-Za-z]+(?:'[A-Za-z]+)?").expect("内置正则表达式有效"),
+            meridiem: Regex::new(r"^\s*(?i:([ap])\.?\s?m\b\.?)").expect("内置正则表达式有效"),
+        }
+    }
+}
+
+impl EnglishItn {
+    pub fn process(&self, text: &str, classes: &Classes) -> String {
+        let tokens: Vec<(usize, usize, String)> = self
+            .word
+            .find_iter(text)
+            .map(|m| (m.start(), m.end(), m.as_str().to_lowercase()))
+            .collect();
+        let spaced = |from: usize, to: usize| from < to && text[from..to].chars().all(|c| c == ' ' || c == '-');
+
+        let mut output = String::with_capacity(text.len());
+        let mut cursor = 0;
+        let mut i = 0;
+        while i < tokens.len() {
+            let is_start = lookup(&tokens[i].2)
+                .is_some_and(|n| !matches!(n.kind, Kind::Hundred | Kind::Scale) && tokens[i].2 != "oh");
+            if !is_start || tokens[i].0 < cursor {
+                i += 1;
+                continue;
+            }
+
+            // 收集连续的数词（允许 hundred and five、three point five）
+            let mut j = i + 1;
+            while j < tokens.len() && spaced(tokens[j - 1].1, tokens[j].0) {
+                let previous = lookup(&tokens[j - 1].2);
+                if previous.is_some_and(|n| n.ordinal) {
+                    break;
+                }
+                let word = tokens[j].2.as_str();
+                let next_is_number = tokens.get(j + 1).is_some_and(|t| lookup(&t.2).is_some() && spaced(tokens[j].1, t.0));
+                let accepted = match word {
+                    "and" => previous.is_some_and(|n| matches!(n.kind, Kind::Hundred | Kind::Scale)) && next_is_number,
+                    "point" => next_is_number,
+                    _ => lookup(word).is_some(),
+                };
+                if !accepted {
+                    break;
+                }
+                j += 1;
+            }
+
+            let words: Vec<&str> = tokens[i..j].iter().map(|t| t.2.as_str()).collect();
+            let previous = i.checked_sub(1).map(|p| &tokens[p]).filter(|p| spaced(p.1, tokens[i].0));
+            let context = Context {
+                text,
+                tokens: &tokens,
+                start: tokens[i].0,
+                end: tokens[j - 1].1,
+                next: j,
+                previous: previous.map(|p| p.2.as_str()),
+            };
+
+            let converted = Spoken::parse(&words).and_then(|spoken| self.convert(&spoken, &context, classes));
+            if let Some((replacement, end)) = converted {
+                output.push_str(&text[cursor..context.start]);
+                output.push_str(&replacement);
+                cursor = end;
+            }
+            i = j;
+        }
+        output.push_str(&text[cursor..]);
+        output
+    }
+
+    /// 根据前后文决定写法，返回替换文本和替换范围的结束位置
+    fn convert(&self, spoken: &Spoken, context: &Context, classes: &Classes) -> Option<(String, usize)> {
+        let next = context.next_word(0);
+
+        // 百分比
+        if classes.contains(ItnClass::Percent) {
+            if let Some((_, end)) = next.filter(|(w, _)| *w == "percent") {
+                return Some((format!("{}%", spoken.digits()), end));
+            }
+        }
+
+        // 货币：five dollars and twenty cents → $5.20
+        if classes.contains(ItnClass::Currency) {
+            if let Some((symbol, end)) = next.and_then(|(w, end)| {
+                CURRENCIES.iter().find(|(name, _)| *name == w).map(|(_, symbol)| (*symbol, end))
+            }) {
+                let cents = context.cents();
+                return Some(match cents {
+                    Some((cents, end)) if spoken.fraction.is_none() => {
+                        (format!("{}{}.{:02}", symbol, spoken.digits(), cents), end)
+                    }
+                    _ => (format!("{}{}", symbol, spoken.digits()), end),
+                });
+            }
+        }
+
+        // 时间：three thirty pm → 3:30 PM，seven o'clock → 7:00
+        if classes.contains(ItnClass::Time) && spoken.fraction.is_none() && !spoken.ordinal {
+            let hour = spoken.chunks[0];
+            if (1..=12).contains(&hour) {
+                if let Some((_, end)) = next.filter(|(w, _)| *w == "o'clock") {
+                    return (spoken.chunks.len() == 1).then(|| (format!("{}:00", hour), end));
+                }
+                if let Some(caps) = self.meridiem.captures(&context.text[context.end..]) {
+                    let end = context.end + caps[0].len();
+                    let suffix = format!("{}M", caps[1].to_uppercase());
+                    let minutes: String = spoken.chunks[1..].iter().map(|c| c.to_string()).collect();
+                    return match minutes.parse::<u64>() {
+                        Ok(minutes) if minutes < 60 => Some((format!("{}:{:02} {}", hour, minutes, suffix), end)),
+                        Ok(_) => None,
+                        Err(_) => Some((format!("{} {}", hour, suffix), end)),
+                    };
+                }
+            }
+        }
+
+        // 日期：October eighteenth → October 18
+        if classes.contains(ItnClass::Date) && context.previous.is_some_and(|p| MONTHS.contains(&p)) {
+            if let Some(day) = spoken.value().filter(|d| (1..=31).contains(d)) {
+                if spoken.fraction.is_none() {
+                    return Some((day.to_string(), context.end));
+                }
+            }
+        }
+
+        // 度量单位
+        if classes.contains(ItnClass::Unit) && next.is_some_and(|(w, _)| UNITS.contains(&w)) && !spoken.ordinal {
+            return Some((spoken.digits(), context.end));
+        }
+
+        // 逐位读出的号码
+        if spoken.chunks.len() >= 7 && spoken.chunks.iter().all(|&c| c < 10) && spoken.fraction.is_none() {
+            return classes.contains(ItnClass::Phone).then(|| (spoken.digits(), context.end));
+        }
+
+        // 序数词：twenty first → 21st（十以下保持英文）
+        if spoken.ordinal {
+            let value = spoken.value().filter(|&v| v >= 10)?;
+            return classes
+                .contains(ItnClass::Ordinal)
+                .then(|| (format!("{}{}", value, ordinal_suffix(value)), context.end));
+        }
+
+        // 基数词：十以下保持英文，年份（twenty twenty six）按段拼接
+        if !classes.contains(ItnClass::Cardinal) {
+            return None;
+        }
+        let convert = match spoken.value() {
+            Some(value) => value >= 10 || spoken.fraction.is_some(),
+            None => spoken.chunks.iter().all(|&c| c < 100) && spoken.digits().len() >= 3,
+        };
+        convert.then(|| (spoken.digits(), context.end))
+    }
+}
+
+/// 一串数词前后的文本
+struct Context<'a> {
+    text: &'a str,
+    tokens: &'a [(usize, usize, String)],
+    start: usize,
+    end: usize,
+    /// 数词之后第一个词的序号
+    next: usize,
+    previous: Option<&'a str>,
+}
+
+impl Context<'_> {
+    /// 数词之后第 `offset` 个词（只隔着空格时）
+    fn next_word(&self, offset: usize) -> Option<(&str, usize)> {
+        let mut end = self.end;
+        for (index, (start, token_end, word)) in self.tokens.iter().enumerate().skip(self.next) {
+            if !self.text[end..*start].chars().all(|c| c == ' ') {
+                return None;
+            }
+            if index == self.next + offset {
+                return Some((word.as_str(), *token_end));
+            }
+            end = *token_end;
+        }
+        None
+    }
+
+    /// 货币名称后的 `and twenty cents`
+    fn cents(&self) -> Option<(u64, usize)> {
+        let mut words = Vec::new();
+        let mut offset = 1;
+        if self.next_word(offset)?.0 == "and" {
+            offset += 1;
+        }
+        loop {
+            let (word, end) = self.next_word(offset)?;
+            if word == "cents" || word == "cent" {
+                let cents = Spoken::parse(&words)?.value().filter(|&c| c < 100)?;
+                return Some((cents, end));
+            }
+            lookup(word)?;
+            words.push(word);
+            offset += 1;
+        }
+    }
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn itn(text: &str) -> String {
+        EnglishItn::default().process(text, &Classes::all())
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = |text: &str| Spoken::parse(&text.split(' ').collect::<Vec<_>>()).map(|s| s.digits());
+        assert_eq!(parse("twenty three").as_deref(), Some("23"));
+        assert_eq!(parse("one hundred and five").as_deref(), Some("105"));
+        assert_eq!(parse("two thousand twenty six").as_deref(), Some("2026"));
+        assert_eq!(parse("twenty twenty six").as_deref(), Some("2026"));
+        assert_eq!(parse("nineteen oh five").as_deref(), Some("1905"));
+        assert_eq!(parse("three point one four").as_deref(), Some("3.14"));
+        assert_eq!(parse("one million two hundred thousand").as_deref(), Some("1200000"));
+    }
+
+    #[test]
+    fn test_cardinal_and_ordinal() {
+        assert_eq!(itn("I have twenty three apples"), "I have 23 apples");
+        assert_eq!(itn("one of the two"), "one of the two");
+        assert_eq!(itn("she came twenty first"), "she came 21st");
+        assert_eq!(itn("the first time"), "the first time");
+        assert_eq!(itn("Forty-two"), "42");
+        assert_eq!(itn("the year twenty twenty six"), "the year 2026");
+    }
+
+    #[test]
+    fn test_context() {
+        assert_eq!(itn("five percent"), "5%");
+        assert_eq!(itn("it costs five dollars and twenty cents"), "it costs $5.20");
+        assert_eq!(itn("ten euros"), "€10");
+        assert_eq!(itn("meet at three thirty pm"), "meet at 3:30 PM");
+        assert_eq!(itn("seven o'clock"), "7:00");
+        assert_eq!(itn("nine a.m. sharp"), "9 AM sharp");
+        assert_eq!(itn("on October eighteenth"), "on October 18");
+        assert_eq!(itn("ran five kilometers"), "ran 5 kilometers");
+        assert_eq!(itn("call five five five one two three four"), "call 5551234");
+    }
+}
@@ -0,0 +1,70 @@
+mod chinese;
+mod english;
+
+use chinese::ChineseItn;
+use english::EnglishItn;
+
+use super::TextProcessor;
+use crate::config::ItnClass;
+
+/// 启用的转换类别
+#[derive(Debug, Clone, Default)]
+pub struct Classes(Vec<ItnClass>);
+
+impl Classes {
+    pub fn all() -> Self {
+        Self(ItnClass::ALL.to_vec())
+    }
+
+    pub fn contains(&self, class: ItnClass) -> bool {
+        self.0.contains(&class)
+    }
+}
+
+impl From<&[ItnClass]> for Classes {
+    fn from(classes: &[ItnClass]) -> Self {
+        Self(classes.to_vec())
+    }
+}
+
+/// 反向文本规范化（ITN）
+///
+/// 把口语中的数词写成阿拉伯数字：日期、时间、百分比、金额、号码、
+/// 度量单位、序数和基数。讯飞云的 `nunum` 只转换部分数字，这里补全其余情况。
+pub struct InverseNormalizer {
+    classes: Classes,
+    chinese: ChineseItn,
+    english: EnglishItn,
+}
+
+impl InverseNormalizer {
+    pub fn new(classes: &[ItnClass]) -> Self {
+        Self {
+            classes: Classes::from(classes),
+            chinese: ChineseItn::default(),
+            english: EnglishItn::default(),
+        }
+    }
+}
+
+impl TextProcessor for InverseNormalizer {
+    fn name(&self) -> &'static str {
+        "itn"
+    }
+
+    fn process(&self, text: &str) -> String {
+        let text = self.chinese.process(text, &self.classes);
+        self.english.process(&text, &self.classes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_text() {
+        let itn = InverseNormalizer::new(&ItnClass::ALL);
+        assert_eq!(itn.process("会议定在十月二十号，大约twenty five people"), "会议定在10月20号，大约25 people");
+    }
+}
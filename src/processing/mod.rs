@@ -0,0 +1,178 @@
+pub mod basic;
+pub mod capitalize;
+pub mod disfluency;
+pub mod itn;
+pub(crate) mod phrase;
+pub mod punctuation;
+pub mod replace;
+pub mod script;
+pub mod spacing;
+pub mod spoken;
+pub mod vocabulary;
+pub mod width;
+
+pub use basic::{StripTrailingPeriod, Trim};
+pub use capitalize::Capitalize;
+pub use disfluency::Disfluency;
+pub use itn::InverseNormalizer;
+pub use punctuation::PunctuationNormalizer;
+pub use replace::Replacer;
+pub use script::ScriptConverter;
+pub use spacing::Spacing;
+pub use spoken::SpokenPunctuation;
+pub use vocabulary::Vocabulary;
+pub use width::WidthConverter;
+
+use std::path::PathBuf;
+
+use crate::config::{Config, ProcessingConfig, Stage};
+use crate::utils::Result;
+
+/// 文本后处理步骤
+///
+/// 识别结果在输出前依次经过 [`Pipeline`] 中的各个步骤。
+pub trait TextProcessor: Send + Sync {
+    /// 步骤名称（用于日志）
+    fn name(&self) -> &'static str;
+
+    /// 处理文本
+    fn process(&self, text: &str) -> String;
+}
+
+/// 按配置顺序执行的处理链
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn TextProcessor>>,
+}
+
+impl Pipeline {
+    /// 根据配置创建（跳过未启用的步骤）
+    ///
+    /// 替换规则中的正则表达式无效时返回错误。
+    pub fn from_config(config: &ProcessingConfig) -> Result<Self> {
+        let stages = config
+            .stages
+            .iter()
+            .filter(|stage| stage.enabled)
+            .map(|stage| build_stage(&stage.stage))
+            .collect::<Result<_>>()?;
+        Ok(Self { stages })
+    }
+
+    /// 追加一个步骤
+    pub fn push(&mut self, stage: impl TextProcessor + 'static) {
+        self.stages.push(Box::new(stage));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// 依次执行所有步骤
+    pub fn process(&self, text: &str) -> String {
+        self.stages.iter().fold(text.to_string(), |text, stage| {
+            let processed = stage.process(&text);
+            if processed != text {
+                tracing::debug!("{}: {:?} → {:?}", stage.name(), text, processed);
+            }
+            processed
+        })
+    }
+}
+
+fn build_stage(stage: &Stage) -> Result<Box<dyn TextProcessor>> {
+    let stage: Box<dyn TextProcessor> = match stage {
+        Stage::Trim => Box::new(Trim),
+        Stage::SpokenPunctuation {
+            builtin,
+            commands,
+            escape,
+        } => Box::new(SpokenPunctuation::new(*builtin, commands, escape)),
+        Stage::Disfluency { fillers, repeats } => Box::new(Disfluency::new(fillers, *repeats)),
+        Stage::Vocabulary { path, threshold } => {
+            let path = match path {
+                Some(path) => PathBuf::from(shellexpand::tilde(path).into_owned()),
+                None => Config::vocabulary_path(),
+            };
+            Box::new(Vocabulary::load(&path, *threshold))
+        }
+        Stage::Itn { classes } => Box::new(InverseNormalizer::new(classes)),
+        Stage::Punctuation { style } => Box::new(PunctuationNormalizer::new(*style)),
+        Stage::Width { to } => Box::new(WidthConverter::new(*to)),
+        Stage::Spacing { numbers } => Box::new(Spacing::new(*numbers)),
+        Stage::Capitalize => Box::new(Capitalize),
+        Stage::ChineseScript { to } => Box::new(ScriptConverter::new(*to)),
+        Stage::Replace { rules } => Box::new(Replacer::new(rules)?),
+        Stage::StripTrailingPeriod { max_chars } => Box::new(StripTrailingPeriod::new(*max_chars)),
+    };
+    Ok(stage)
+}
+
+/// 是否为中日韩文字或全角标点
+pub(crate) fn is_cjk(c: char) -> bool {
+    is_han(c)
+        || matches!(c,
+            '\u{3000}'..='\u{303F}'   // 中文标点
+            | '\u{FF01}'..='\u{FF0F}' // 全角标点
+            | '\u{FF1A}'..='\u{FF20}'
+        )
+}
+
+/// 是否为汉字
+pub(crate) fn is_han(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'     // 基本汉字
+        | '\u{3400}'..='\u{4DBF}'   // 扩展 A
+        | '\u{F900}'..='\u{FAFF}'   // 兼容汉字
+        | '\u{20000}'..='\u{2A6DF}' // 扩展 B
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PunctuationStyle, StageConfig, WidthTarget};
+
+    #[test]
+    fn test_pipeline_order_and_enabled() {
+        let config = ProcessingConfig {
+            stages: vec![
+                StageConfig {
+                    enabled: true,
+                    stage: Stage::Trim,
+                },
+                StageConfig {
+                    enabled: true,
+                    stage: Stage::Punctuation {
+                        style: PunctuationStyle::Chinese,
+                    },
+                },
+                StageConfig {
+                    enabled: false,
+                    stage: Stage::Width { to: WidthTarget::Half },
+                },
+                StageConfig {
+                    enabled: true,
+                    stage: Stage::StripTrailingPeriod { max_chars: 4 },
+                },
+            ],
+        };
+        let pipeline = Pipeline::from_config(&config).unwrap();
+
+        assert_eq!(pipeline.process("  好的.  "), "好的");
+        assert_eq!(pipeline.process("今天天气很好,出去走走吧."), "今天天气很好，出去走走吧。");
+        // 宽度转换未启用
+        assert_eq!(pipeline.process("ＡＢＣ"), "ＡＢＣ");
+    }
+
+    #[test]
+    fn test_default_pipeline() {
+        // 默认只去掉首尾空白，没有 [processing] 的旧配置升级后输出不变
+        let pipeline = Pipeline::from_config(&ProcessingConfig::default()).unwrap();
+        assert_eq!(pipeline.process(" 版本２ ,好吗? "), "版本２ ,好吗?");
+        assert_eq!(pipeline.process("ｗｉｆｉ密码是１２３"), "ｗｉｆｉ密码是１２３");
+        assert_eq!(pipeline.process("e.g. this"), "e.g. this");
+        assert_eq!(pipeline.process("一万年太久"), "一万年太久");
+        assert!(Pipeline::default().is_empty());
+    }
+}
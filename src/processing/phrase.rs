@@ -0,0 +1,54 @@
+//! 口述命令的说法匹配（口述标点、Markdown 模式和代码模式共用）
+
+/// 与命令首尾的字组成的常用词：命令落在这些词中间时不算命令
+/// （"交换行李" 中的 "换行"、"部分号码" 中的 "分号"）
+const OVERLAPPING_WORDS: &[&str] = &[
+    // …换 / 行…
+    "交换", "转换", "变换", "切换", "替换", "更换", "调换", "互换", "兑换", "置换",
+    "行李", "行业", "行人", "行为", "行动", "行政", "行程", "行走", "行驶", "行情", "行列", "行使", "行星",
+    // …句 / …分 / …问 / …冒 / …叹 / 号…
+    "语句", "词句", "诗句", "部分", "充分", "十分", "百分", "过分", "区分", "评分", "积分", "学分",
+    "疑问", "提问", "询问", "访问", "学问", "顾问", "假冒", "仿冒", "感冒", "赞叹", "惊叹",
+    "号码", "号召", "号称", "号角", "号脉",
+];
+
+/// 在字节位置 `at` 处匹配说法，返回结束位置
+///
+/// 中文说法要求不拆开常用词，英文说法要求整词且不区分大小写。
+pub(crate) fn match_phrase(text: &str, at: usize, phrase: &str) -> Option<usize> {
+    let end = at + phrase.len();
+    let candidate = text.get(at..end)?;
+
+    if !phrase.is_ascii() {
+        return (candidate == phrase && !splits_word(text, at, end)).then_some(end);
+    }
+
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    let boundary = !is_word(text[..at].chars().next_back()) && !is_word(text[end..].chars().next());
+    (boundary && candidate.eq_ignore_ascii_case(phrase)).then_some(end)
+}
+
+/// `start..end` 的边界是否落在某个常用词中间
+fn splits_word(text: &str, start: usize, end: usize) -> bool {
+    OVERLAPPING_WORDS.iter().any(|word| {
+        text.match_indices(word).any(|(position, word)| {
+            let word_end = position + word.len();
+            (position < start && start < word_end) || (position < end && end < word_end)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_phrase() {
+        assert_eq!(match_phrase("第一行换行第二行", 9, "换行"), Some(15));
+        assert_eq!(match_phrase("交换行李", 3, "换行"), None);
+        assert_eq!(match_phrase("a New Line b", 2, "new line"), Some(10));
+        assert_eq!(match_phrase("newline", 0, "new"), None);
+        // 不在字符边界上
+        assert_eq!(match_phrase("换行", 1, "行"), None);
+    }
+}
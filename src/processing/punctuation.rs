@@ -0,0 +1,180 @@
+use super::{is_cjk, TextProcessor};
+use crate::config::PunctuationStyle;
+
+/// 中英文对应的标点
+const PAIRS: [(char, char); 6] = [
+    (',', '，'),
+    ('.', '。'),
+    ('?', '？'),
+    ('!', '！'),
+    (':', '：'),
+    (';', '；'),
+];
+
+/// 标点规范化
+///
+/// - `Chinese`：中文语境中的英文标点换成全角，去掉标点前后多余的空格，
+///   合并重复标点（`，，` → `，`、`，。` → `。`）；连续的英文句点是省略号（`...` → `……`）
+/// - `Ascii`：全角标点换成英文标点，并在句读后补一个空格
+pub struct PunctuationNormalizer {
+    style: PunctuationStyle,
+}
+
+impl PunctuationNormalizer {
+    pub fn new(style: PunctuationStyle) -> Self {
+        Self { style }
+    }
+}
+
+impl TextProcessor for PunctuationNormalizer {
+    fn name(&self) -> &'static str {
+        "punctuation"
+    }
+
+    fn process(&self, text: &str) -> String {
+        match self.style {
+            PunctuationStyle::Chinese => to_chinese(text),
+            PunctuationStyle::Ascii => to_ascii(text),
+        }
+    }
+}
+
+fn to_chinese(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+
+        // 省略号作为一个整体：中文语境写成 "……"，否则保持原样
+        if c == '.' && chars.get(i) == Some(&'.') {
+            let start = i - 1;
+            while chars.get(i) == Some(&'.') {
+                i += 1;
+            }
+            let prev = output.trim_end_matches(is_space).chars().last();
+            let next = chars[i..].iter().copied().find(|&c| !is_space(c));
+            if prev.is_some_and(is_cjk) || next.is_some_and(is_cjk) {
+                output.truncate(output.trim_end_matches(is_space).len());
+                output.push_str("……");
+            } else {
+                output.extend(&chars[start..i]);
+            }
+            continue;
+        }
+
+        let full = match PAIRS.iter().find(|(ascii, full)| c == *ascii || c == *full) {
+            Some(&(ascii, full)) => {
+                let prev = output.trim_end_matches(is_space).chars().last();
+                let next = chars[i..].iter().copied().find(|&c| !is_space(c));
+                let prev_cjk = prev.is_some_and(is_cjk);
+                let next_cjk = next.is_some_and(is_cjk);
+                // 3.14、v1.2 这类数字和英文中的点保持原样
+                let in_word = ascii == '.' && prev.is_some_and(|c| c.is_ascii_alphanumeric());
+                if c == full || prev_cjk || (next_cjk && !in_word) {
+                    full
+                } else {
+                    output.push(c);
+                    continue;
+                }
+            }
+            None => {
+                output.push(c);
+                continue;
+            }
+        };
+
+        // 去掉标点前后的空格（保留换行）
+        output.truncate(output.trim_end_matches(is_space).len());
+        while chars.get(i).is_some_and(|&c| is_space(c)) {
+            i += 1;
+        }
+
+        match output.chars().last() {
+            Some(last) if last == full => {}
+            // 逗号后紧跟句末标点时保留后者
+            Some(last @ ('，' | '：' | '；')) if full != '，' => {
+                output.truncate(output.len() - last.len_utf8());
+                output.push(full);
+            }
+            _ => output.push(full),
+        }
+    }
+    output
+}
+
+fn to_ascii(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let ascii = match c {
+            '、' => ',',
+            '（' => '(',
+            '）' => ')',
+            '“' | '”' => '"',
+            '‘' | '’' => '\'',
+            _ => match PAIRS.iter().find(|(_, full)| c == *full) {
+                Some(&(ascii, _)) => ascii,
+                None => {
+                    output.push(c);
+                    continue;
+                }
+            },
+        };
+        output.push(ascii);
+
+        // 句读后补空格（后面已有空格、标点或到结尾时不补）
+        if matches!(ascii, ',' | '.' | '?' | '!' | ':' | ';')
+            && chars
+                .peek()
+                .is_some_and(|next| !next.is_whitespace() && !next.is_ascii_punctuation() && !is_closing(*next))
+        {
+            output.push(' ');
+        }
+    }
+    output
+}
+
+/// 行内空白（不含换行）
+fn is_space(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\u{3000}')
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(c, '）' | '”' | '’' | '，' | '。' | '？' | '！' | '：' | '；' | '、')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chinese_style() {
+        let stage = PunctuationNormalizer::new(PunctuationStyle::Chinese);
+        assert_eq!(stage.process("你好,世界."), "你好，世界。");
+        assert_eq!(stage.process("真的吗 ? 太好了 !"), "真的吗？太好了！");
+        assert_eq!(stage.process("好的，，我知道了。。"), "好的，我知道了。");
+        assert_eq!(stage.process("是吗，？"), "是吗？");
+        // 英文和数字中的标点保持不变
+        assert_eq!(stage.process("圆周率是3.14"), "圆周率是3.14");
+        assert_eq!(stage.process("Hello, world."), "Hello, world.");
+        assert_eq!(stage.process("升级到v1.2版本"), "升级到v1.2版本");
+        assert_eq!(stage.process("版本2 ,好吗"), "版本2，好吗");
+        assert_eq!(stage.process("第一行.\n第二行"), "第一行。\n第二行");
+        // 省略号不是句号
+        assert_eq!(stage.process("等等..."), "等等……");
+        assert_eq!(stage.process("他说...好吧"), "他说……好吧");
+        assert_eq!(stage.process("Wait..."), "Wait...");
+    }
+
+    #[test]
+    fn test_ascii_style() {
+        let stage = PunctuationNormalizer::new(PunctuationStyle::Ascii);
+        assert_eq!(stage.process("Hello，world。"), "Hello, world.");
+        assert_eq!(stage.process("苹果、香蕉（水果）！"), "苹果, 香蕉(水果)!");
+        assert_eq!(stage.process("他说：“好。”"), "他说: \"好.\"");
+    }
+}
@@ -0,0 +1,97 @@
+use regex::{NoExpand, Regex};
+
+use super::TextProcessor;
+use crate::config::ReplaceRule;
+use crate::utils::{Result, VoiceInputError};
+
+/// 按顺序执行的替换规则
+///
+/// 字面规则原样替换；正则规则支持捕获组（`$1`、`${name}`）。
+/// 替换文本可以是多行片段，如说 "签名模板" 展开成完整签名。
+pub struct Replacer {
+    rules: Vec<(Regex, String, bool)>,
+}
+
+impl Replacer {
+    pub fn new(rules: &[ReplaceRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .filter(|rule| !rule.from.is_empty())
+            .map(|rule| {
+                let pattern = if rule.regex {
+                    rule.from.clone()
+                } else {
+                    regex::escape(&rule.from)
+                };
+                let regex = Regex::new(&pattern).map_err(|e| {
+                    VoiceInputError::Config(format!("替换规则 {:?} 不是有效的正则表达式: {}", rule.from, e))
+                })?;
+                Ok((regex, rule.to.clone(), rule.regex))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+}
+
+impl TextProcessor for Replacer {
+    fn name(&self) -> &'static str {
+        "replace"
+    }
+
+    fn process(&self, text: &str) -> String {
+        self.rules
+            .iter()
+            .fold(text.to_string(), |text, (regex, to, expand)| {
+                if *expand {
+                    regex.replace_all(&text, to.as_str()).into_owned()
+                } else {
+                    regex.replace_all(&text, NoExpand(to)).into_owned()
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(from: &str, to: &str, regex: bool) -> ReplaceRule {
+        ReplaceRule {
+            from: from.to_string(),
+            to: to.to_string(),
+            regex,
+        }
+    }
+
+    #[test]
+    fn test_literal_and_snippet() {
+        let replacer = Replacer::new(&[
+            rule("我的邮箱", "zhang.san@example.com", false),
+            rule("签名模板", "张三\n研发部\n电话：$100", false),
+        ])
+        .unwrap();
+        assert_eq!(replacer.process("请发到我的邮箱"), "请发到zhang.san@example.com");
+        // 字面规则中的 $ 不当作捕获组
+        assert_eq!(replacer.process("签名模板"), "张三\n研发部\n电话：$100");
+    }
+
+    #[test]
+    fn test_regex_captures_in_order() {
+        let replacer = Replacer::new(&[
+            rule(r"(\d+)号楼(\d+)室", "$1-$2", true),
+            rule(r"(?P<n>\d+)-(?P<room>\d+)", "${n}栋${room}", true),
+            rule("栋", "幢", false),
+        ])
+        .unwrap();
+        // 后面的规则作用于前面规则的结果
+        assert_eq!(replacer.process("送到3号楼502室"), "送到3幢502");
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let err = Replacer::new(&[rule("(未闭合", "", true)]).err().unwrap();
+        assert!(matches!(err, VoiceInputError::Config(_)));
+        // 同样的文字作为字面规则是合法的
+        assert!(Replacer::new(&[rule("(未闭合", "", false)]).is_ok());
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use super::TextProcessor;
+use crate::config::ScriptTarget;
+
+/// 简 → 繁单字对照
+const CHARS: &str = include_str!("data/chars.txt");
+/// 简 → 繁词组（修正一简对多繁）
+const PHRASES: &str = include_str!("data/phrases.txt");
+/// 繁体 → 台湾用语 / 异体字
+const TW_PHRASES: &str = include_str!("data/tw_phrases.txt");
+const TW_VARIANTS: &str = include_str!("data/tw_variants.txt");
+/// 繁体 → 香港用语 / 异体字
+const HK_PHRASES: &str = include_str!("data/hk_phrases.txt");
+const HK_VARIANTS: &str = include_str!("data/hk_variants.txt");
+
+/// 简体中也在用、繁转简时不应改写的字（答覆 → 答复，但 覆盖 仍是 覆）
+const AMBIGUOUS: &[char] = &['覆', '著'];
+
+/// 简繁转换（OpenCC 风格：词组最长匹配优先，其余逐字对照）
+///
+/// 转为台湾 / 香港繁体时先做通用繁体转换，再替换地区用语和异体字。
+pub struct ScriptConverter {
+    chain: Vec<Dictionary>,
+}
+
+impl ScriptConverter {
+    pub fn new(to: ScriptTarget) -> Self {
+        let chain = match to {
+            ScriptTarget::Simplified => vec![Dictionary::simplified()],
+            ScriptTarget::Traditional => vec![Dictionary::traditional()],
+            ScriptTarget::ZhTw => vec![Dictionary::traditional(), Dictionary::regional(TW_PHRASES, TW_VARIANTS)],
+            ScriptTarget::ZhHk => vec![Dictionary::traditional(), Dictionary::regional(HK_PHRASES, HK_VARIANTS)],
+        };
+        Self { chain }
+    }
+}
+
+impl TextProcessor for ScriptConverter {
+    fn name(&self) -> &'static str {
+        "chinese_script"
+    }
+
+    fn process(&self, text: &str) -> String {
+        self.chain
+            .iter()
+            .fold(text.to_string(), |text, dictionary| dictionary.convert(&text))
+    }
+}
+
+/// 一步转换使用的词典
+#[derive(Default)]
+struct Dictionary {
+    chars: HashMap<char, char>,
+    phrases: HashMap<String, String>,
+    /// 最长词组的字数
+    max_phrase: usize,
+}
+
+impl Dictionary {
+    fn traditional() -> Self {
+        let mut dictionary = Self::default();
+        dictionary.add_chars(CHARS);
+        dictionary.add_phrases(PHRASES);
+        dictionary
+    }
+
+    fn regional(phrases: &str, variants: &str) -> Self {
+        let mut dictionary = Self::default();
+        dictionary.add_chars(variants);
+        dictionary.add_phrases(phrases);
+        dictionary
+    }
+
+    /// 由简转繁的对照表反推
+    fn simplified() -> Self {
+        let mut chars = HashMap::new();
+        for (simplified, traditional) in char_pairs(CHARS) {
+            chars.entry(traditional).or_insert(simplified);
+        }
+        for (simplified, traditional) in phrase_pairs(PHRASES) {
+            for (s, t) in simplified.chars().zip(traditional.chars()) {
+                if s != t && !AMBIGUOUS.contains(&t) {
+                    chars.entry(t).or_insert(s);
+                }
+            }
+        }
+        // 地区异体字：裡 → 裏 → 里
+        for (common, variant) in char_pairs(TW_VARIANTS).chain(char_pairs(HK_VARIANTS)) {
+            if AMBIGUOUS.contains(&variant) {
+                continue;
+            }
+            if let Some(&simplified) = chars.get(&common) {
+                chars.entry(variant).or_insert(simplified);
+            }
+        }
+
+        Self {
+            chars,
+            ..Self::default()
+        }
+    }
+
+    fn add_chars(&mut self, data: &str) {
+        self.chars.extend(char_pairs(data));
+    }
+
+    fn add_phrases(&mut self, data: &str) {
+        for (from, to) in phrase_pairs(data) {
+            self.max_phrase = self.max_phrase.max(from.chars().count());
+            self.phrases.insert(from.to_string(), to.to_string());
+        }
+    }
+
+    fn convert(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut output = String::with_capacity(text.len());
+
+        let mut i = 0;
+        'outer: while i < chars.len() {
+            for len in (2..=self.max_phrase.min(chars.len() - i)).rev() {
+                let key: String = chars[i..i + len].iter().collect();
+                if let Some(phrase) = self.phrases.get(&key) {
+                    output.push_str(phrase);
+                    i += len;
+                    continue 'outer;
+                }
+            }
+            output.push(self.chars.get(&chars[i]).copied().unwrap_or(chars[i]));
+            i += 1;
+        }
+        output
+    }
+}
+
+/// 词典文件中的有效内容（跳过注释）
+fn entries(data: &str) -> impl Iterator<Item = &str> {
+    data.lines().filter(|line| !line.starts_with('#'))
+}
+
+/// 单字对照：每项两个字
+fn char_pairs(data: &str) -> impl Iterator<Item = (char, char)> + '_ {
+    entries(data).flat_map(str::split_whitespace).filter_map(|pair| {
+        let mut chars = pair.chars();
+        Some((chars.next()?, chars.next()?))
+    })
+}
+
+/// 词组对照：每行两列
+fn phrase_pairs(data: &str) -> impl Iterator<Item = (&str, &str)> {
+    entries(data).filter_map(|line| {
+        let mut columns = line.split_whitespace();
+        Some((columns.next()?, columns.next()?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(to: ScriptTarget, text: &str) -> String {
+        ScriptConverter::new(to).process(text)
+    }
+
+    #[test]
+    fn test_traditional() {
+        assert_eq!(convert(ScriptTarget::Traditional, "我们的软件"), "我們的軟件");
+        // 一简对多繁按词组区分
+        assert_eq!(convert(ScriptTarget::Traditional, "头发干净，干活吃面条"), "頭髮乾淨，幹活吃麵條");
+        assert_eq!(convert(ScriptTarget::Traditional, "后来发现里面"), "後來發現裏面");
+        assert_eq!(convert(ScriptTarget::Traditional, "Rust 1.80"), "Rust 1.80");
+    }
+
+    #[test]
+    fn test_regional_variants() {
+        assert_eq!(convert(ScriptTarget::ZhTw, "这个软件的数据库在里面"), "這個軟體的資料庫在裡面");
+        assert_eq!(convert(ScriptTarget::ZhTw, "下载视频"), "下載影片");
+        assert_eq!(convert(ScriptTarget::ZhHk, "坐出租车去说明会"), "坐的士去説明會");
+        assert_eq!(convert(ScriptTarget::ZhHk, "一碗面条"), "一碗麪條");
+    }
+
+    #[test]
+    fn test_simplified() {
+        assert_eq!(convert(ScriptTarget::Simplified, "頭髮乾淨，這個裡面"), "头发干净，这个里面");
+        assert_eq!(convert(ScriptTarget::Simplified, "覆蓋"), "覆盖");
+        assert_eq!(convert(ScriptTarget::Simplified, "説明"), "说明");
+    }
+}
@@ -0,0 +1,66 @@
+use super::{is_han, TextProcessor};
+
+/// 中英文之间加空格（盘古之白）："我们用Rust写tokio服务" → "我们用 Rust 写 tokio 服务"
+///
+/// 只在汉字与字母、数字直接相邻时插入空格，标点两侧不加。
+pub struct Spacing {
+    numbers: bool,
+}
+
+impl Spacing {
+    /// `numbers` 为 false 时数字与汉字之间不加空格
+    pub fn new(numbers: bool) -> Self {
+        Self { numbers }
+    }
+
+    /// 英文一侧的字符（含 C++、C#、50% 中的符号）
+    fn is_latin(&self, c: char) -> bool {
+        c.is_ascii_alphabetic()
+            || matches!(c, '+' | '#')
+            || (self.numbers && (c.is_ascii_digit() || c == '%'))
+    }
+}
+
+impl TextProcessor for Spacing {
+    fn name(&self) -> &'static str {
+        "spacing"
+    }
+
+    fn process(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len() + 8);
+        let mut prev: Option<char> = None;
+        for c in text.chars() {
+            if let Some(prev) = prev {
+                if (is_han(prev) && self.is_latin(c)) || (self.is_latin(prev) && is_han(c)) {
+                    result.push(' ');
+                }
+            }
+            result.push(c);
+            prev = Some(c);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spacing() {
+        let spacing = Spacing::new(true);
+        assert_eq!(spacing.process("我们用Rust写tokio服务"), "我们用 Rust 写 tokio 服务");
+        assert_eq!(spacing.process("版本2发布了"), "版本 2 发布了");
+        assert_eq!(spacing.process("增长了50%的用户"), "增长了 50% 的用户");
+        assert_eq!(spacing.process("用C++和C#写"), "用 C++ 和 C# 写");
+        // 已有空格、标点两侧不重复添加
+        assert_eq!(spacing.process("用 Rust，很好"), "用 Rust，很好");
+        assert_eq!(spacing.process("（Rust）"), "（Rust）");
+    }
+
+    #[test]
+    fn test_spacing_without_numbers() {
+        let spacing = Spacing::new(false);
+        assert_eq!(spacing.process("2026年用Rust"), "2026年用 Rust");
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+
+use super::phrase::match_phrase;
+use super::TextProcessor;
+
+/// 内置的口述命令（说法 → 替换文本）
+const BUILTIN: &[(&str, &str)] = &[
+    ("逗号", "，"),
+    ("句号", "。"),
+    ("问号", "？"),
+    ("感叹号", "！"),
+    ("叹号", "！"),
+    ("冒号", "："),
+    ("分号", "；"),
+    ("顿号", "、"),
+    ("省略号", "……"),
+    ("破折号", "——"),
+    ("百分号", "%"),
+    ("左括号", "（"),
+    ("右括号", "）"),
+    ("左引号", "“"),
+    ("右引号", "”"),
+    ("左书名号", "《"),
+    ("右书名号", "》"),
+    ("换行", "\n"),
+    ("新段落", "\n\n"),
+    ("另起一段", "\n\n"),
+    ("comma", ","),
+    ("period", "."),
+    ("full stop", "."),
+    ("question mark", "?"),
+    ("exclamation mark", "!"),
+    ("exclamation point", "!"),
+    ("colon", ":"),
+    ("semicolon", ";"),
+    ("open parenthesis", "("),
+    ("close parenthesis", ")"),
+    ("open quote", "\""),
+    ("close quote", "\""),
+    ("new line", "\n"),
+    ("newline", "\n"),
+    ("new paragraph", "\n\n"),
+];
+
+/// 识别引擎自动添加的标点（与口述命令相邻时去掉，避免重复）
+const AUTO_PUNCTUATION: &[char] = &['，', '。', '？', '！', '、', '；', '：', ',', '.', '?', '!', ';', ':'];
+
+/// 左侧成对标点（后面不留空格）
+const OPENING: &[char] = &['（', '“', '‘', '《', '(', '"', '[', '{'];
+
+/// 口述标点与排版命令
+///
+/// 把 "逗号"、"换行"、"new paragraph" 等说法换成对应的符号。中文命令不拆开常用词，
+/// 英文命令按整词匹配（不区分大小写）。在命令前说转义词（如 "字面 逗号"）
+/// 则保留命令原文。
+pub struct SpokenPunctuation {
+    /// 按长度从长到短排列，保证 "感叹号" 先于 "叹号" 匹配
+    commands: Vec<(String, String)>,
+    escape: Vec<String>,
+}
+
+impl SpokenPunctuation {
+    pub fn new(builtin: bool, custom: &BTreeMap<String, String>, escape: &[String]) -> Self {
+        let mut table = BTreeMap::new();
+        if builtin {
+            for &(phrase, replacement) in BUILTIN {
+                table.insert(phrase.to_string(), replacement.to_string());
+            }
+        }
+        for (phrase, replacement) in custom {
+            table.insert(phrase.trim().to_lowercase(), replacement.clone());
+        }
+
+        let mut commands: Vec<(String, String)> =
+            table.into_iter().filter(|(phrase, _)| !phrase.is_empty()).collect();
+        commands.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.chars().count()));
+
+        Self {
+            commands,
+            escape: escape.iter().map(|e| e.to_lowercase()).filter(|e| !e.is_empty()).collect(),
+        }
+    }
+
+    /// 在 `i` 处匹配命令，返回命令序号和结束位置
+    fn find_command(&self, text: &str, i: usize) -> Option<(usize, usize)> {
+        self.commands
+            .iter()
+            .enumerate()
+            .find_map(|(index, (phrase, _))| match_phrase(text, i, phrase).map(|end| (index, end)))
+    }
+}
+
+impl TextProcessor for SpokenPunctuation {
+    fn name(&self) -> &'static str {
+        "spoken_punctuation"
+    }
+
+    fn process(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut i = 0;
+
+        while i < text.len() {
+            // 转义：保留紧随其后的命令原文
+            if let Some(end) = self.escape.iter().find_map(|e| match_phrase(text, i, e)) {
+                let next = skip(text, end, |c| c.is_whitespace() || AUTO_PUNCTUATION.contains(&c));
+                if let Some((_, command_end)) = self.find_command(text, next) {
+                    output.push_str(&text[next..command_end]);
+                    i = command_end;
+                    continue;
+                }
+            }
+
+            if let Some((index, end)) = self.find_command(text, i) {
+                let replacement = &self.commands[index].1;
+                i = apply(&mut output, replacement, text, end);
+                continue;
+            }
+
+            let c = text[i..].chars().next().unwrap_or_default();
+            output.push(c);
+            i += c.len_utf8();
+        }
+        output
+    }
+}
+
+/// 写入替换文本，返回继续处理的位置
+fn apply(output: &mut String, replacement: &str, text: &str, end: usize) -> usize {
+    let first = replacement.chars().next().unwrap_or_default();
+
+    if first == '\n' {
+        // 换行 / 分段：去掉前后的空格和命令后多余的标点
+        output.truncate(output.trim_end_matches([' ', '\t']).len());
+        output.push_str(replacement);
+        return skip(text, end, |c| c.is_whitespace() || AUTO_PUNCTUATION.contains(&c));
+    }
+
+    if OPENING.contains(&first) {
+        output.push_str(replacement);
+        return skip(text, end, |c| c == ' ');
+    }
+
+    if replacement.chars().all(|c| c.is_ascii_punctuation() || is_cjk_punctuation(c)) {
+        // 句读：贴紧前文，替换掉引擎在命令前后自动添加的标点
+        output.truncate(output.trim_end_matches([' ', '\t']).len());
+        output.truncate(output.trim_end_matches(AUTO_PUNCTUATION).len());
+        output.push_str(replacement);
+        return skip(text, end, |c| AUTO_PUNCTUATION.contains(&c));
+    }
+
+    output.push_str(replacement);
+    end
+}
+
+/// 跳过满足条件的字符
+fn skip(text: &str, from: usize, predicate: impl Fn(char) -> bool) -> usize {
+    text[from..]
+        .char_indices()
+        .find(|&(_, c)| !predicate(c))
+        .map_or(text.len(), |(offset, _)| from + offset)
+}
+
+fn is_cjk_punctuation(c: char) -> bool {
+    matches!(c, '\u{3000}'..='\u{303F}' | '\u{FF01}'..='\u{FF0F}' | '\u{FF1A}'..='\u{FF20}' | '…' | '—' | '”' | '’')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage() -> SpokenPunctuation {
+        let custom = BTreeMap::from([("艾特".to_string(), "@".to_string())]);
+        SpokenPunctuation::new(true, &custom, &["字面".to_string(), "literal".to_string()])
+    }
+
+    #[test]
+    fn test_chinese_commands() {
+        let stage = stage();
+        assert_eq!(stage.process("今天天气很好逗号我们出去吧感叹号"), "今天天气很好，我们出去吧！");
+        assert_eq!(stage.process("第一行换行第二行新段落第三段"), "第一行\n第二行\n\n第三段");
+        // 引擎自动添加的标点不重复
+        assert_eq!(stage.process("你好，逗号，世界句号。"), "你好，世界。");
+        assert_eq!(stage.process("增长了百分号"), "增长了%");
+        assert_eq!(stage.process("他说冒号左引号好的右引号"), "他说：“好的”");
+    }
+
+    #[test]
+    fn test_commands_inside_words() {
+        let stage = stage();
+        assert_eq!(stage.process("交换行李"), "交换行李");
+        assert_eq!(stage.process("他想转换行业"), "他想转换行业");
+        assert_eq!(stage.process("部分号码打不通"), "部分号码打不通");
+        assert_eq!(stage.process("有疑问号码发给我"), "有疑问号码发给我");
+        // 紧挨着普通的字时仍然是命令
+        assert_eq!(stage.process("先交换换行再说"), "先交换\n再说");
+    }
+
+    #[test]
+    fn test_english_commands() {
+        let stage = stage();
+        assert_eq!(stage.process("hello comma world period"), "hello, world.");
+        assert_eq!(stage.process("Is it ready Question Mark"), "Is it ready?");
+        assert_eq!(stage.process("first new line second"), "first\nsecond");
+        // 只匹配整词
+        assert_eq!(stage.process("periodic colonel"), "periodic colonel");
+    }
+
+    #[test]
+    fn test_escape() {
+        let stage = stage();
+        assert_eq!(stage.process("这里要写字面逗号两个字"), "这里要写逗号两个字");
+        assert_eq!(stage.process("字面 换行"), "换行");
+        assert_eq!(stage.process("say literal comma please"), "say comma please");
+        // 转义词后面不是命令时原样保留
+        assert_eq!(stage.process("字面意思"), "字面意思");
+    }
+
+    #[test]
+    fn test_custom_commands() {
+        let stage = stage();
+        assert_eq!(stage.process("邮箱是me艾特example.com"), "邮箱是me@example.com");
+
+        let only_custom = SpokenPunctuation::new(false, &BTreeMap::new(), &[]);
+        assert_eq!(only_custom.process("逗号"), "逗号");
+    }
+}
@@ -0,0 +1,287 @@
+pub(crate) mod pinyin;
+
+use std::path::Path;
+
+use regex::Regex;
+
+use self::pinyin::{syllable_score, PinyinTable};
+use super::TextProcessor;
+
+/// 用户词表：把同音误识别的人名、产品名、术语改回正确写法
+///
+/// 词表每行一个词条，`#` 开头或空格后的 `#` 为注释。中文词条按拼音模糊匹配
+/// （忽略声调，容忍平翘舌、鼻边音、前后鼻音等混淆，每个音节都要达到阈值）；
+/// 两个字的词条太容易撞上普通词语，要求读音完全相同，且前后都是标点、空白或文本边界；
+/// 可以用 `词条 = 拼音` 指定读音，例如 `Kimi = qi mi`；
+/// 其余词条（如 `GitHub`）忽略大小写和词内空格匹配。
+pub struct Vocabulary {
+    table: PinyinTable,
+    /// 按拼音匹配的词条（音节多的在前）
+    entries: Vec<Entry>,
+    /// 按字面匹配的词条
+    literals: Vec<(Regex, String)>,
+    /// 每个音节的最低相似度
+    threshold: f64,
+}
+
+/// 按拼音匹配的词条
+struct Entry {
+    text: String,
+    /// 每个音节的可能读音
+    syllables: Vec<Vec<String>>,
+}
+
+impl Vocabulary {
+    /// 读取词表文件（文件不存在时为空词表）
+    pub fn load(path: &Path, threshold: f64) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!("读取词表 {} 失败: {}", path.display(), e);
+                }
+                String::new()
+            }
+        };
+        Self::parse(&content, threshold)
+    }
+
+    /// 解析词表内容
+    pub fn parse(content: &str, threshold: f64) -> Self {
+        let table = PinyinTable::default();
+        let mut entries = Vec::new();
+        let mut literals = Vec::new();
+        for line in content.lines() {
+            // 行尾注释（前面要有空白，避免截断 C# 之类的词）
+            let line = line.split(" #").next().unwrap_or_default().trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (text, pinyin) = match line.split_once('=') {
+                Some((text, pinyin)) => (text.trim(), Some(pinyin)),
+                None => (line, None),
+            };
+            let syllables = match pinyin {
+                Some(pinyin) => Some(
+                    pinyin
+                        .split_whitespace()
+                        .map(|syllable| vec![syllable.to_lowercase()])
+                        .collect(),
+                ),
+                None => text
+                    .chars()
+                    .map(|c| table.readings(c).map(|readings| readings.iter().map(|r| r.to_string()).collect()))
+                    .collect::<Option<Vec<_>>>(),
+            };
+            match syllables {
+                Some(syllables) if !syllables.is_empty() => entries.push(Entry {
+                    text: text.to_string(),
+                    syllables,
+                }),
+                _ => match literal_pattern(text) {
+                    Some(pattern) => literals.push((pattern, text.to_string())),
+                    None => tracing::warn!("词表词条无法识别，已跳过: {}", line),
+                },
+            }
+        }
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.syllables.len()));
+        Self {
+            table,
+            entries,
+            literals,
+            threshold,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.literals.is_empty()
+    }
+
+    /// 窗口与词条的拼音相似度：取最不像的音节（有音节达不到阈值时为 None）
+    fn score(&self, window: &[char], entry: &Entry) -> Option<f64> {
+        // 两个字的词条太容易撞上普通词语，要求读音完全相同
+        let threshold = if entry.syllables.len() <= 2 { 1.0 } else { self.threshold };
+        let mut lowest: f64 = 1.0;
+        for (c, expected) in window.iter().zip(&entry.syllables) {
+            let readings = self.table.readings(*c)?;
+            let best = readings
+                .iter()
+                .flat_map(|a| expected.iter().map(move |b| syllable_score(a, b)))
+                .fold(0.0, f64::max);
+            if best < threshold {
+                return None;
+            }
+            lowest = lowest.min(best);
+        }
+        Some(lowest)
+    }
+
+
+
+    /// 替换与词条读音相近的汉字片段
+    fn correct(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        // 窗口前后是否为词边界（标点、空白、非汉字或文本边界）
+        let is_boundary = |index: Option<usize>| index.and_then(|i| chars.get(i)).is_none_or(|c| !is_han(*c));
+        let standalone = |start: usize, end: usize| is_boundary(start.checked_sub(1)) && is_boundary(Some(end));
+        let mut result = String::with_capacity(text.len());
+        let mut i = 0;
+        'outer: while i < chars.len() {
+            let candidates = self.entries.iter().filter(|entry| i + entry.syllables.len() <= chars.len());
+
+            // 已经是词表中的写法
+            for entry in candidates.clone() {
+                let window = &chars[i..i + entry.syllables.len()];
+                if window.iter().copied().eq(entry.text.chars()) {
+                    result.push_str(&entry.text);
+                    i += window.len();
+                    continue 'outer;
+                }
+            }
+
+            let mut best: Option<(f64, &Entry)> = None;
+            for entry in candidates {
+                let window = &chars[i..i + entry.syllables.len()];
+                // 两个字的词条只在单独成词时替换（"这张为你"、"黎明时分" 不改）
+                if window.len() <= 2 && !standalone(i, i + window.len()) {
+                    continue;
+                }
+                if let Some(score) = self.score(window, entry) {
+                    if best.is_none_or(|(best, _)| score > best) {
+                        best = Some((score, entry));
+                    }
+                }
+            }
+            match best {
+                Some((_, entry)) => {
+                    result.push_str(&entry.text);
+                    i += entry.syllables.len();
+                }
+                None => {
+                    result.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+        result
+    }
+}
+
+impl TextProcessor for Vocabulary {
+    fn name(&self) -> &'static str {
+        "vocabulary"
+    }
+
+    fn process(&self, text: &str) -> String {
+        if self.is_empty() {
+            return text.to_string();
+        }
+        let text = self
+            .literals
+            .iter()
+            .fold(text.to_string(), |text, (pattern, replacement)| {
+                pattern.replace_all(&text, regex::NoExpand(replacement)).into_owned()
+            });
+        self.correct(&text)
+    }
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}')
+}
+
+/// 字面词条的匹配规则：忽略大小写，字符之间可以有空格或连字符
+fn literal_pattern(text: &str) -> Option<Regex> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let (first, last) = (chars.first()?, chars.last()?);
+    let body = chars
+        .iter()
+        .map(|c| regex::escape(&c.to_string()))
+        .collect::<Vec<_>>()
+        .join(r"[\s-]?");
+    let start = if first.is_alphanumeric() { r"\b" } else { "" };
+    let end = if last.is_alphanumeric() { r"\b" } else { "" };
+    Regex::new(&format!("(?i){}{}{}", start, body, end)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: &str = "
+        # 同事和产品
+        张伟
+        科大讯飞
+        智谱清言
+        南京银行
+        Kimi = qi mi    # 指定读音
+        GitHub
+        C#
+    ";
+
+    fn vocabulary() -> Vocabulary {
+        Vocabulary::parse(WORDS, 0.8)
+    }
+
+    #[test]
+    fn test_homophones() {
+        let vocabulary = vocabulary();
+        assert_eq!(vocabulary.process("我用科大迅飞的接口"), "我用科大讯飞的接口");
+        assert_eq!(vocabulary.process("章伟，你看一下"), "张伟，你看一下");
+        assert_eq!(vocabulary.process("试试只普清言"), "试试智谱清言");
+    }
+
+    #[test]
+    fn test_fuzzy_initials_and_finals() {
+        let vocabulary = vocabulary();
+        // z/zh、前后鼻音、n/l
+        assert_eq!(vocabulary.process("自谱亲言"), "智谱清言");
+        assert_eq!(vocabulary.process("存到兰京银行"), "存到南京银行");
+    }
+
+    #[test]
+    fn test_unrelated_text_untouched() {
+        let vocabulary = vocabulary();
+        assert_eq!(vocabulary.process("今天天气很好"), "今天天气很好");
+        assert_eq!(vocabulary.process("张伟已经写对了"), "张伟已经写对了");
+        assert_eq!(vocabulary.process("伟大的科学家"), "伟大的科学家");
+    }
+
+    #[test]
+    fn test_similar_words_untouched() {
+        let vocabulary = vocabulary();
+        // 两个字的词条要求读音相同
+        assert_eq!(vocabulary.process("赞伟来了"), "赞伟来了");
+        assert_eq!(vocabulary.process("大家先站位"), "大家先站位");
+        assert_eq!(vocabulary.process("藏尾巴"), "藏尾巴");
+        // 读音相同但不是单独成词
+        assert_eq!(vocabulary.process("这个账为什么不对"), "这个账为什么不对");
+        assert_eq!(vocabulary.process("上涨为主"), "上涨为主");
+
+        let names = Vocabulary::parse("张伟\n李明\n王芳", 0.8);
+        assert_eq!(names.process("这张为你准备的"), "这张为你准备的");
+        assert_eq!(names.process("黎明时分出发"), "黎明时分出发");
+        assert_eq!(names.process("他往放学的路上走"), "他往放学的路上走");
+        assert_eq!(names.process("请问李明在吗"), "请问李明在吗");
+        assert_eq!(names.process("黎明，你好"), "李明，你好");
+        assert_eq!(names.process("联系人：王方"), "联系人：王芳");
+        // 每个音节都要足够相似，不能靠其他音节拉高平均分
+        assert_eq!(vocabulary.process("南京一行人"), "南京一行人");
+    }
+
+    #[test]
+    fn test_pinyin_override_and_literal() {
+        let vocabulary = vocabulary();
+        assert_eq!(vocabulary.process("问一下，奇米"), "问一下，Kimi");
+        assert_eq!(vocabulary.process("push 到 github 上"), "push 到 GitHub 上");
+        assert_eq!(vocabulary.process("git hub 仓库"), "GitHub 仓库");
+        assert_eq!(vocabulary.process("用 c# 写"), "用 C# 写");
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let vocabulary = Vocabulary::load(Path::new("/nonexistent/vocabulary.txt"), 0.8);
+        assert!(vocabulary.is_empty());
+        assert_eq!(vocabulary.process("科大迅飞"), "科大迅飞");
+    }
+}
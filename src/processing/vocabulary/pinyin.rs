@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+/// 常用汉字拼音（不带声调，多音字有多个读音）
+const TABLE: &str = include_str!("data/pinyin.txt");
+
+/// 声母（双字母在前，保证最长匹配）
+const INITIALS: &[&str] = &[
+    "zh", "ch", "sh", "b", "p", "m", "f", "d", "t", "n", "l", "g", "k", "h", "j", "q", "x", "r", "z", "c",
+    "s", "y", "w",
+];
+
+/// 汉字 → 拼音读音
+pub struct PinyinTable {
+    readings: HashMap<char, Vec<&'static str>>,
+}
+
+impl Default for PinyinTable {
+    fn default() -> Self {
+        let mut readings: HashMap<char, Vec<&'static str>> = HashMap::new();
+        for line in TABLE.lines().filter(|line| !line.starts_with('#')) {
+            let Some((syllable, chars)) = line.split_once(' ') else {
+                continue;
+            };
+            for c in chars.trim().chars() {
+                let entry = readings.entry(c).or_default();
+                if !entry.contains(&syllable) {
+                    entry.push(syllable);
+                }
+            }
+        }
+        Self { readings }
+    }
+}
+
+impl PinyinTable {
+    /// 单字的全部读音
+    pub fn readings(&self, c: char) -> Option<&[&'static str]> {
+        self.readings.get(&c).map(Vec::as_slice)
+    }
+}
+
+/// 两个音节的相似度
+///
+/// 完全相同为 1.0；只差平翘舌、鼻边音、前后鼻音等常见混淆为 0.8；
+/// 声母或韵母之一相同为 0.4；否则为 0。
+pub fn syllable_score(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let (a_initial, a_final) = split(a);
+    let (b_initial, b_final) = split(b);
+    let initial = fuzzy_initial(a_initial) == fuzzy_initial(b_initial);
+    let fin = fuzzy_final(a_final) == fuzzy_final(b_final);
+    match (initial, fin) {
+        (true, true) => 0.8,
+        (true, false) | (false, true) => 0.4,
+        (false, false) => 0.0,
+    }
+}
+
+/// 拆分声母和韵母（零声母时声母为空）
+fn split(syllable: &str) -> (&str, &str) {
+    INITIALS
+        .iter()
+        .find(|initial| syllable.starts_with(*initial))
+        .map(|initial| syllable.split_at(initial.len()))
+        .unwrap_or(("", syllable))
+}
+
+/// 模糊声母：z/zh、c/ch、s/sh、n/l、f/h、r/l
+fn fuzzy_initial(initial: &str) -> &str {
+    match initial {
+        "zh" => "z",
+        "ch" => "c",
+        "sh" => "s",
+        "n" | "r" => "l",
+        "h" => "f",
+        other => other,
+    }
+}
+
+/// 模糊韵母：前后鼻音 an/ang、en/eng、in/ing、ian/iang、uan/uang
+fn fuzzy_final(fin: &str) -> &str {
+    match fin {
+        "ang" | "eng" | "ing" | "iang" | "uang" => &fin[..fin.len() - 1],
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readings() {
+        let table = PinyinTable::default();
+        assert_eq!(table.readings('讯'), Some(&["xun"][..]));
+        assert_eq!(table.readings('行'), Some(&["hang", "xing"][..]));
+        assert_eq!(table.readings('A'), None);
+    }
+
+    #[test]
+    fn test_syllable_score() {
+        assert_eq!(syllable_score("zhang", "zhang"), 1.0);
+        assert_eq!(syllable_score("zhang", "zang"), 0.8);
+        assert_eq!(syllable_score("nan", "lang"), 0.8);
+        assert_eq!(syllable_score("xin", "xing"), 0.8);
+        assert_eq!(syllable_score("hu", "fu"), 0.8);
+        assert_eq!(syllable_score("an", "ang"), 0.8);
+        assert_eq!(syllable_score("ma", "mi"), 0.4);
+        assert_eq!(syllable_score("ma", "ke"), 0.0);
+    }
+}
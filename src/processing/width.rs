@@ -0,0 +1,67 @@
+use super::TextProcessor;
+use crate::config::WidthTarget;
+
+/// 全角与半角之间的偏移量（`Ａ` - `A`）
+const OFFSET: u32 = 0xFEE0;
+
+/// 全角 / 半角转换
+///
+/// 只转换字母、数字、空格和常见符号，中文标点（，。！？：；（）等）保持不变，
+/// 标点交给 [`super::PunctuationNormalizer`] 处理。
+pub struct WidthConverter {
+    to: WidthTarget,
+}
+
+impl WidthConverter {
+    pub fn new(to: WidthTarget) -> Self {
+        Self { to }
+    }
+}
+
+impl TextProcessor for WidthConverter {
+    fn name(&self) -> &'static str {
+        "width"
+    }
+
+    fn process(&self, text: &str) -> String {
+        match self.to {
+            WidthTarget::Half => text.chars().map(to_half).collect(),
+            WidthTarget::Full => text.chars().map(to_full).collect(),
+        }
+    }
+}
+
+fn to_half(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '！' | '（' | '）' | '，' | '：' | '；' | '？' => c,
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - OFFSET).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn to_full(c: char) -> char {
+    if c.is_ascii_alphanumeric() {
+        char::from_u32(c as u32 + OFFSET).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_half() {
+        let stage = WidthConverter::new(WidthTarget::Half);
+        assert_eq!(stage.process("ＡＢＣ　１２３％"), "ABC 123%");
+        assert_eq!(stage.process("好的，（真的）？"), "好的，（真的）？");
+    }
+
+    #[test]
+    fn test_to_full() {
+        let stage = WidthConverter::new(WidthTarget::Full);
+        assert_eq!(stage.process("第1名 ABC!"), "第１名 ＡＢＣ!");
+    }
+}
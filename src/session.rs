@@ -0,0 +1,366 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use chrono::Local;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::history::{HistoryEntry, HistoryStore};
+use crate::input::pick_alternatives;
+use crate::mode::{code, command, edit::MAX_REVISIONS, Edit, MarkdownDocument, Mode, Revisions};
+use crate::online::{RecognitionEvent, XfyunRealtimeRecognizer};
+use crate::output::{
+    ClipboardOutput, ConfidenceMarker, DesktopNotifier, FileOutput, StreamingTyper, TypingOutput,
+};
+use crate::processing::Pipeline;
+use crate::transcript::Transcript;
+use crate::utils::{Result, VoiceInputError};
+use crate::Config;
+
+/// 听写会话选项
+#[derive(Debug, Clone, Default)]
+pub struct SessionOptions {
+    /// 流式输出：边识别边向焦点窗口输入文字
+    pub streaming: bool,
+    /// 外部运行标志（None 时使用 Ctrl+C 停止）
+    pub running: Option<Arc<AtomicBool>>,
+    /// 识别事件转发通道
+    pub events: Option<UnboundedSender<RecognitionEvent>>,
+    /// 覆盖配置中的静音自动停止时长（秒）
+    pub silence_duration: Option<f32>,
+    /// 输出前在终端中选择候选词
+    pub pick: bool,
+    /// 听写模式
+    pub mode: Mode,
+    /// 最近的结果，供 "把X改成Y"、"撤销" 等编辑命令修改（为空时从历史记录读取）
+    pub revisions: Option<Arc<Mutex<Revisions>>>,
+    /// 常驻进程共用的桌面通知，让各次会话的通知原地替换（None 时本次会话单独连接）
+    pub notifier: Option<DesktopNotifier>,
+}
+
+/// 按配置连接桌面通知（未开启或连接失败时为 None）
+pub async fn connect_notifier(config: &Config) -> Option<DesktopNotifier> {
+    if !config.notification.enabled {
+        return None;
+    }
+    match DesktopNotifier::connect().await {
+        Ok(notifier) => Some(notifier),
+        Err(e) => {
+            tracing::warn!("桌面通知不可用: {}", e);
+            None
+        }
+    }
+}
+
+/// 执行一次完整的听写会话：录音识别 → 输出
+///
+/// 识别结果会复制到剪贴板；开启流式输出时还会输入到当前焦点窗口。
+/// 开启桌面通知时，开始录音、识别结果和错误都会以同一条通知显示；
+/// 开启历史记录时，非空结果会保存到历史数据库。
+pub async fn run_session(config: &Config, options: SessionOptions) -> Result<Transcript> {
+    let notifier = match options.notifier.clone() {
+        _ if !config.notification.enabled => None,
+        Some(notifier) => Some(notifier),
+        None => connect_notifier(config).await,
+    };
+
+    let started = Instant::now();
+    let mode = options.mode;
+    let output = match (config.output.to_file(), options.streaming) {
+        _ if mode == Mode::Command => "command",
+        (true, false) => "file",
+        (true, true) => "file+typing",
+        (false, false) => "clipboard",
+        (false, true) => "clipboard+typing",
+    };
+    let language = match options.mode {
+        Mode::Code => config.code.language.clone(),
+        _ => config.xfyun.language.clone(),
+    };
+    let (result, edit) = match dictate(config, options, notifier.clone()).await {
+        Ok((transcript, edit)) => (Ok(transcript), edit),
+        Err(e) => (Err(e), None),
+    };
+
+    // 编辑结果作为原文的修订版本写入历史，单次运行时下一条编辑命令从这里接着改；
+    // "撤销" 删掉最近的修订版本
+    if let (true, Ok(transcript)) = (config.history.enabled, &result) {
+        match &edit {
+            Some(Edit::Undo) => remove_last_revision(config).await,
+            _ if transcript.is_empty() => {}
+            _ => {
+                let entry = HistoryEntry {
+                    id: 0,
+                    created_at: Local::now(),
+                    engine: "xfyun".to_string(),
+                    language,
+                    duration: started.elapsed().as_secs_f64(),
+                    output: output.to_string(),
+                    text: transcript.text(),
+                    // 分段必须与保存的文本一致，否则 history show / export 会显示原始识别结果；
+                    // 编辑结果与本次录音的内容无关，不保存分段
+                    segments: match edit {
+                        Some(_) => Vec::new(),
+                        None => transcript.text_segments(),
+                    },
+                    revision_of: None,
+                };
+                save_history(config, entry, edit.is_some()).await;
+            }
+        }
+    }
+
+    if let Some(notifier) = &notifier {
+        let sent = match &result {
+            // 命令模式在执行时已经通知
+            Ok(_) if mode == Mode::Command => Ok(()),
+            Ok(transcript) if config.output.to_file() && !transcript.is_empty() => {
+                match file_output(config) {
+                    Ok(file) => notifier.saved_to_file(&transcript.text(), file.path()).await,
+                    Err(e) => Err(e),
+                }
+            }
+            Ok(transcript) => notifier.result(&transcript.text()).await,
+            Err(e) => notifier.error(e).await,
+        };
+        if let Err(e) = sent {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    result
+}
+
+/// 保存到历史数据库（失败只记录日志，不影响本次输出）
+async fn save_history(config: &Config, entry: HistoryEntry, revision: bool) {
+    let path = config.history.db_path();
+    let saved = tokio::task::spawn_blocking(move || {
+        let store = HistoryStore::open(path)?;
+        if revision {
+            store.add_revision(&entry)
+        } else {
+            store.add(&entry)
+        }
+    })
+    .await;
+    match saved {
+        Ok(Ok(id)) => tracing::info!("已保存到历史记录 #{}", id),
+        Ok(Err(e)) => tracing::warn!("{}", e),
+        Err(e) => tracing::warn!("保存历史记录失败: {}", e),
+    }
+}
+
+/// 撤销时删掉历史中最近的修订版本（失败只记录日志）
+async fn remove_last_revision(config: &Config) {
+    let path = config.history.db_path();
+    let removed = tokio::task::spawn_blocking(move || HistoryStore::open(path)?.remove_last_revision()).await;
+    match removed {
+        Ok(Ok(true)) => tracing::info!("已从历史记录中撤销最近一次编辑"),
+        Ok(Ok(false)) => {}
+        Ok(Err(e)) => tracing::warn!("{}", e),
+        Err(e) => tracing::warn!("更新历史记录失败: {}", e),
+    }
+}
+
+/// 录音识别并输出，同时返回本次应用的编辑命令
+async fn dictate(
+    config: &Config,
+    options: SessionOptions,
+    notifier: Option<DesktopNotifier>,
+) -> Result<(Transcript, Option<Edit>)> {
+    if !config.xfyun.is_configured() {
+        return Err(VoiceInputError::Authentication(
+            "请在 config.toml 中配置讯飞云 API 密钥（app_id, api_secret, api_key）".to_string(),
+        ));
+    }
+
+    // 创建讯飞云实时识别器（代码模式使用偏向英文的识别语言，并关闭引擎标点）
+    let (language, punctuation) = match options.mode {
+        Mode::Code => (&config.code.language, false),
+        _ => (&config.xfyun.language, config.xfyun.punctuation),
+    };
+    let mut recognizer = XfyunRealtimeRecognizer::new(
+        config.xfyun.app_id.clone(),
+        config.xfyun.api_secret.clone(),
+        config.xfyun.api_key.clone(),
+    )
+    .with_language(language, &config.xfyun.accent)
+    .with_candidates(config.xfyun.candidates)
+    .with_punctuation(punctuation)
+    .with_stop_keywords(&config.xfyun.stop_keywords);
+    if let Some(running) = options.running {
+        recognizer = recognizer.with_running_flag(running);
+    }
+
+    // 文本后处理（每次会话重新读取配置；代码模式改用命名风格和符号语法）
+    let pipeline = Arc::new(match options.mode {
+        Mode::Code => code::pipeline(&config.code),
+        _ => Pipeline::from_config(&config.processing)?,
+    });
+
+    // 识别事件：流式输出时把已稳定的文字（经过后处理）输入到焦点窗口，并转发给调用方
+    let mut typer = if options.streaming && options.mode != Mode::Command {
+        Some(StreamingTyper::new(TypingOutput::new(&config.output.typing_tool)?))
+    } else {
+        None
+    };
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<RecognitionEvent>();
+    recognizer = recognizer.with_event_sender(tx);
+    let forward = options.events.clone();
+    let runtime = tokio::runtime::Handle::current();
+    let stream_pipeline = pipeline.clone();
+    let recording_notifier = notifier.clone();
+    let event_task = tokio::task::spawn_blocking(move || {
+        while let Some(event) = rx.blocking_recv() {
+            if let (Some(notifier), RecognitionEvent::Recording) = (&recording_notifier, &event) {
+                if let Err(e) = runtime.block_on(notifier.recording_started()) {
+                    tracing::warn!("{}", e);
+                }
+            }
+            if let (Some(typer), RecognitionEvent::Partial { stable, .. }) = (typer.as_mut(), &event) {
+                if let Err(e) = typer.update(&stream_pipeline.process(stable)) {
+                    tracing::warn!("流式输入失败: {}", e);
+                }
+            }
+            if let Some(forward) = &forward {
+                forward.send(event).ok();
+            }
+        }
+        typer
+    });
+
+    // 实时识别（边录边发送）
+    let mut transcript = recognizer
+        .recognize_realtime(
+            config.audio.sample_rate,
+            options.silence_duration.unwrap_or(config.whisper.silence_duration),
+        )
+        .await?;
+
+    // 关闭推送通道，补齐最后一段文字
+    drop(recognizer);
+    let typer = event_task
+        .await
+        .map_err(|e| anyhow::anyhow!("事件处理任务失败: {}", e))?;
+
+    // 选择候选词（流式输出已输入的部分会在下面按差异修正）
+    if options.pick {
+        transcript = tokio::task::spawn_blocking(move || {
+            pick_alternatives(&mut transcript).map(|_| transcript)
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("候选词选择失败: {}", e))??;
+    }
+    if !pipeline.is_empty() {
+        transcript.processed = Some(pipeline.process(&transcript.raw_text()));
+    }
+
+    // Markdown 模式：转换结构命令并追加到累积的文档
+    let document = if options.mode == Mode::Markdown {
+        let mut document = MarkdownDocument::open(config.markdown.document_path())?;
+        let added = document.append(&transcript.text());
+        document.save()?;
+        transcript.processed = Some(added.trim_start_matches('\n').to_string());
+        Some((document, added))
+    } else {
+        None
+    };
+
+    // 编辑命令：修改上一次结果，修改后的全文重新复制到剪贴板
+    let edited = match Edit::parse(&transcript.text()) {
+        Some(edit) if options.mode == Mode::Dictation && config.editing.enabled => {
+            let revisions = options.revisions.clone().unwrap_or_default();
+            let empty = revisions.lock().unwrap().current().is_none();
+            if empty {
+                *revisions.lock().unwrap() = load_revisions(config).await;
+            }
+            let mut revisions = revisions.lock().unwrap();
+            let previous = revisions.current().map(str::to_string);
+            let edited = revisions.apply(&edit);
+            match &edited {
+                Some(_) => tracing::info!("编辑上一次结果: {:?}", edit),
+                None => tracing::info!("编辑命令无法应用于上一次结果，按普通文字输出: {:?}", edit),
+            }
+            edited.map(|text| (edit, previous, text))
+        }
+        _ => None,
+    };
+    match (&edited, &options.revisions) {
+        (Some((_, _, text)), _) => transcript.processed = Some(text.clone()),
+        (None, Some(revisions)) if options.mode == Mode::Dictation => {
+            revisions.lock().unwrap().push(transcript.text());
+        }
+        _ => {}
+    }
+
+    // 命令模式：执行匹配的命令，不输出文字
+    if options.mode == Mode::Command {
+        if !transcript.is_empty() {
+            command::run_command(config, &transcript.text(), notifier.as_ref()).await?;
+        }
+        return Ok((transcript, None));
+    }
+
+    let text = transcript.text();
+    if let Some(mut typer) = typer {
+        // 编辑命令本身不留在窗口中
+        typer.update(if edited.is_some() { "" } else { &text })?;
+        tracing::info!("已输入 {} 个字符到当前窗口", typer.typed().chars().count());
+    }
+
+    if config.output.to_file() {
+        // 写入文件（低置信度的词加上标记，方便校对）
+        if !transcript.is_empty() {
+            let content = match (&document, ConfidenceMarker::from_config(&config.confidence)) {
+                // 文件中已有的内容以换行结尾，分隔的空行只需要补一个换行
+                (Some((_, added)), _) => added.strip_prefix('\n').unwrap_or(added).to_string(),
+                (None, Some(marker)) if edited.is_none() => marker.mark(&text, &transcript.segments),
+                (None, _) => text,
+            };
+            // 编辑结果替换文件末尾的上一次结果
+            match &edited {
+                Some((_, Some(previous), _)) => file_output(config)?.replace_last(previous, &content)?,
+                _ => file_output(config)?.append(&content)?,
+            }
+        }
+    } else {
+        // 复制到剪贴板（Markdown 模式复制整篇文档）
+        let mut clipboard = ClipboardOutput::new()?;
+        match &document {
+            Some((document, _)) => clipboard.copy(document.text())?,
+            None => clipboard.copy(&text)?,
+        }
+    }
+
+    Ok((transcript, edited.map(|(edit, _, _)| edit)))
+}
+
+/// 从历史记录读取最近的结果（单次运行或刚启动时没有内存中的状态）
+async fn load_revisions(config: &Config) -> Revisions {
+    if !config.history.enabled {
+        return Revisions::default();
+    }
+    let path = config.history.db_path();
+    let recent = tokio::task::spawn_blocking(move || HistoryStore::open(path)?.recent(MAX_REVISIONS)).await;
+    match recent {
+        Ok(Ok(entries)) => Revisions::new(entries.into_iter().rev().map(|entry| entry.text).collect()),
+        Ok(Err(e)) => {
+            tracing::warn!("{}", e);
+            Revisions::default()
+        }
+        Err(e) => {
+            tracing::warn!("读取历史记录失败: {}", e);
+            Revisions::default()
+        }
+    }
+}
+
+/// `default = "file"` 时的输出文件
+fn file_output(config: &Config) -> Result<FileOutput> {
+    config
+        .output
+        .file_path
+        .as_deref()
+        .map(FileOutput::new)
+        .ok_or_else(|| VoiceInputError::Config("[output] default = \"file\" 时需要设置 file_path".to_string()))
+}
@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+
+/// 一次识别的完整结果：分段 → 词
+///
+/// 时间均为相对录音开始的毫秒数。分段和词保留识别引擎的原始结果，
+/// 后处理（标点、格式等）的输出保存在 `processed` 中。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub segments: Vec<Segment>,
+    /// 已发送音频的总时长
+    pub duration_ms: u64,
+    /// 后处理之后的文本（为 None 时即各分段拼接的原始文本）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processed: Option<String>,
+}
+
+/// 识别结果中的一个分段（一句话）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// 分段内的词（旧版本保存的历史记录没有）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<Word>,
+}
+
+/// 一个词及其时间和置信度
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Word {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// 识别引擎给出的置信度分数（未提供时为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    /// 其他候选词（按可能性从高到低）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<String>,
+}
+
+impl Transcript {
+    /// 最终输出的文本（有后处理结果时优先使用）
+    pub fn text(&self) -> String {
+        self.processed.clone().unwrap_or_else(|| self.raw_text())
+    }
+
+    /// 识别引擎返回的原始文本
+    pub fn raw_text(&self) -> String {
+        self.segments.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    /// 与 [`text`](Self::text) 一致的分段：后处理改动了文本时，
+    /// 把处理后的文本按原文的分段边界重新切开，每段保留自己的时间
+    pub fn text_segments(&self) -> Vec<Segment> {
+        match &self.processed {
+            Some(processed) if *processed != self.raw_text() => align_segments(&self.segments, processed),
+            _ => self.segments.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text().is_empty()
+    }
+
+    /// 按顺序遍历所有词
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.segments.iter().flat_map(|s| s.words.iter())
+    }
+
+    /// 把第 `word` 个词（全局序号）换成它的第 `alternative` 个候选词
+    ///
+    /// 原来的词会放回候选列表的同一位置，所以可以再换回来。
+    /// 序号越界时返回 false。
+    pub fn choose_alternative(&mut self, word: usize, alternative: usize) -> bool {
+        let mut index = word;
+        for segment in &mut self.segments {
+            if index >= segment.words.len() {
+                index -= segment.words.len();
+                continue;
+            }
+
+            let word = &mut segment.words[index];
+            let Some(candidate) = word.alternatives.get_mut(alternative) else {
+                return false;
+            };
+            std::mem::swap(&mut word.text, candidate);
+            segment.text = segment.words.iter().map(|w| w.text.as_str()).collect();
+            self.processed = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// 对齐时允许的最大字符数乘积（超过时按字数比例切分）
+const MAX_ALIGN_CELLS: usize = 4_000_000;
+
+/// 把改写后的文本按原分段的边界切开
+///
+/// 用最长公共子序列对齐原文和新文本：与原文对得上的字归原来的分段，
+/// 新增的字（标点、改写的内容）跟随前一个对得上的字。
+/// 文字有改动的分段去掉词信息（词是原文的）；切出来为空的分段去掉。
+pub fn align_segments(segments: &[Segment], text: &str) -> Vec<Segment> {
+    let raw: Vec<(char, usize)> = segments
+        .iter()
+        .enumerate()
+        .flat_map(|(index, segment)| segment.text.chars().map(move |c| (c, index)))
+        .collect();
+    let new: Vec<char> = text.chars().collect();
+    if segments.is_empty() || raw.is_empty() {
+        return Vec::new();
+    }
+
+    let owners = if raw.len().saturating_mul(new.len()) <= MAX_ALIGN_CELLS {
+        lcs_owners(&raw, &new)
+    } else {
+        vec![None; new.len()]
+    };
+    // 完全对不上时按字数比例分配
+    let proportional = |k: usize| raw[k * raw.len() / new.len().max(1)].1;
+    let first = owners.iter().flatten().next().copied();
+    let mut current = first;
+    let mut texts = vec![String::new(); segments.len()];
+    for (k, (c, owner)) in new.iter().zip(&owners).enumerate() {
+        if owner.is_some() {
+            current = *owner;
+        }
+        let index = current.unwrap_or_else(|| proportional(k));
+        texts[index].push(*c);
+    }
+
+    segments
+        .iter()
+        .zip(texts)
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(segment, text)| Segment {
+            words: if text == segment.text { segment.words.clone() } else { Vec::new() },
+            text,
+            ..segment.clone()
+        })
+        .collect()
+}
+
+/// 新文本中每个字对应的原分段（对不上的为 None），比较时忽略大小写
+fn lcs_owners(raw: &[(char, usize)], new: &[char]) -> Vec<Option<usize>> {
+    let same = |a: char, b: char| a == b || a.to_lowercase().eq(b.to_lowercase());
+    let (n, m) = (raw.len(), new.len());
+    // lengths[i][j]：raw[i..] 与 new[j..] 的最长公共子序列长度
+    let mut lengths = vec![0u32; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i * (m + 1) + j] = if same(raw[i].0, new[j]) {
+                lengths[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                lengths[(i + 1) * (m + 1) + j].max(lengths[i * (m + 1) + j + 1])
+            };
+        }
+    }
+
+    let mut owners = vec![None; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if same(raw[i].0, new[j]) {
+            owners[j] = Some(raw[i].1);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * (m + 1) + j] >= lengths[i * (m + 1) + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_text_and_words() {
+        let word = |text: &str, start_ms, end_ms| Word {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            score: None,
+            alternatives: Vec::new(),
+        };
+        let transcript = Transcript {
+            segments: vec![
+                Segment {
+                    start_ms: 0,
+                    end_ms: 900,
+                    text: "今天天气".to_string(),
+                    words: vec![word("今天", 0, 400), word("天气", 400, 900)],
+                },
+                Segment {
+                    start_ms: 900,
+                    end_ms: 1500,
+                    text: "很好".to_string(),
+                    words: vec![word("很好", 900, 1500)],
+                },
+            ],
+            duration_ms: 1600,
+            processed: None,
+        };
+
+        assert_eq!(transcript.text(), "今天天气很好");
+        let words: Vec<&str> = transcript.words().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, ["今天", "天气", "很好"]);
+        assert!(!transcript.is_empty());
+        assert!(Transcript::default().is_empty());
+
+        let processed = Transcript {
+            processed: Some("今天天气很好。".to_string()),
+            ..transcript
+        };
+        assert_eq!(processed.text(), "今天天气很好。");
+        assert_eq!(processed.raw_text(), "今天天气很好");
+        // 标点加在所属分段的末尾，分段时间不变
+        let segments = processed.text_segments();
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["今天天气", "很好。"]);
+        assert_eq!((segments[1].start_ms, segments[1].end_ms), (900, 1500));
+        assert_eq!(segments[0].words.len(), 2);
+        assert!(segments[1].words.is_empty());
+
+        let unchanged = Transcript {
+            processed: Some("今天天气很好".to_string()),
+            ..processed
+        };
+        assert_eq!(unchanged.text_segments(), unchanged.segments);
+    }
+
+    #[test]
+    fn test_segment_without_words_deserializes() {
+        let segment: Segment =
+            serde_json::from_str(r#"{"start_ms":0,"end_ms":1000,"text":"旧记录"}"#).unwrap();
+        assert!(segment.words.is_empty());
+    }
+
+    #[test]
+    fn test_choose_alternative() {
+        let word = |text: &str, alternatives: &[&str]| Word {
+            text: text.to_string(),
+            start_ms: 0,
+            end_ms: 0,
+            score: None,
+            alternatives: alternatives.iter().map(|a| a.to_string()).collect(),
+        };
+        let mut transcript = Transcript {
+            segments: vec![
+                Segment {
+                    start_ms: 0,
+                    end_ms: 0,
+                    text: "我们".to_string(),
+                    words: vec![word("我们", &[])],
+                },
+                Segment {
+                    start_ms: 0,
+                    end_ms: 0,
+                    text: "期中开会".to_string(),
+                    words: vec![word("期中", &["其中", "期终"]), word("开会", &[])],
+                },
+            ],
+            duration_ms: 0,
+            processed: None,
+        };
+
+        assert!(transcript.choose_alternative(1, 0));
+        assert_eq!(transcript.text(), "我们其中开会");
+        assert_eq!(transcript.segments[1].words[0].alternatives, ["期中", "期终"]);
+
+        assert!(!transcript.choose_alternative(0, 0));
+        assert!(!transcript.choose_alternative(9, 0));
+    }
+}
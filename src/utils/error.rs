@@ -27,9 +27,27 @@ pub enum VoiceInputError {
     #[error("剪贴板操作失败: {0}")]
     Clipboard(String),
 
+    #[error("输入设备错误: {0}")]
+    InputDevice(String),
+
+    #[error("键盘输入失败: {0}")]
+    Typing(String),
+
+    #[error("桌面通知失败: {0}")]
+    Notification(String),
+
+    #[error("守护进程错误: {0}")]
+    Daemon(String),
+
+    #[error("历史记录错误: {0}")]
+    History(String),
+
     #[error("配置错误: {0}")]
     Config(String),
 
+    #[error("语音命令失败: {0}")]
+    Command(String),
+
     #[error("IO 错误: {0}")]
     Io(#[from] std::io::Error),
 
@@ -73,6 +91,23 @@ impl VoiceInputError {
                     msg
                 )
             }
+            Self::InputDevice(msg) => {
+                format!(
+                    "输入设备错误: {}\n\
+                    读取 /dev/input 需要 input 组权限，可以运行：\n\
+                    sudo usermod -aG input $USER\n\
+                    然后注销并重新登录。",
+                    msg
+                )
+            }
+            Self::Typing(msg) => {
+                format!(
+                    "键盘输入失败: {}\n\
+                    X11 请安装 xdotool，Wayland 请安装 wtype 或 ydotool，\n\
+                    并在配置文件 [output] 段中设置 typing_tool。",
+                    msg
+                )
+            }
             _ => self.to_string(),
         }
     }